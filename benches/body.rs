@@ -0,0 +1,160 @@
+use std::convert::Infallible;
+
+use bytes::Bytes;
+use criterion::{criterion_group, criterion_main, Criterion};
+use http_body_util::{BodyExt, Empty, Full};
+use hyper_1::{
+    body::Incoming,
+    client::conn::http1::{self as client_http1, SendRequest},
+    server::conn::http1,
+    service::service_fn,
+};
+use tokio::runtime::Runtime;
+use tower_hyper_http_body_compat::{
+    AsHyper014Body, HttpBody04ToHttpBody1, HttpBody1ToHttpBody04, Hyper014BodyAsHyper1Body,
+    Hyper1IncomingAsHyper014Body, TokioIoToHyper1Io,
+};
+
+const CHUNKS: usize = 64;
+const CHUNK_SIZE: usize = 1024;
+
+async fn drain_04<B>(body: B)
+where
+    B: http_body_04::Body,
+    B::Error: std::fmt::Debug,
+{
+    tokio::pin!(body);
+    while let Some(chunk) = http_body_04::Body::data(&mut body).await {
+        chunk.unwrap();
+    }
+    http_body_04::Body::trailers(&mut body).await.unwrap();
+}
+
+async fn drain_1<B>(body: B)
+where
+    B: http_body_1::Body,
+    B::Error: std::fmt::Debug,
+{
+    tokio::pin!(body);
+    while let Some(frame) = body.frame().await {
+        frame.unwrap();
+    }
+}
+
+/// The data of an http-body 0.4 `Body` as a `Stream`, the way hyper 0.14's `Body` is usually
+/// created from other bodies.
+fn data_stream<B>(body: B) -> impl futures_util::Stream<Item = Result<Bytes, B::Error>>
+where
+    B: http_body_04::Body<Data = Bytes> + Unpin,
+{
+    futures_util::stream::unfold(body, |mut body| async move {
+        let chunk = http_body_04::Body::data(&mut body).await?;
+        Some((chunk, body))
+    })
+}
+
+/// Connect to a hyper 1.0 server that converts each request's `Incoming` with `convert` and
+/// drains the result through http-body 0.4's API.
+async fn connect<F, B>(convert: F) -> SendRequest<Full<Bytes>>
+where
+    F: Fn(Incoming) -> B + Copy + Send + 'static,
+    B: http_body_04::Body + Send + 'static,
+    B::Error: std::fmt::Debug,
+{
+    let (client, server) = tokio::io::duplex(CHUNKS * CHUNK_SIZE);
+
+    tokio::spawn(async move {
+        let svc = service_fn(move |req: hyper_1::Request<Incoming>| async move {
+            drain_04(convert(req.into_body())).await;
+            Ok::<_, Infallible>(hyper_1::Response::new(Empty::<Bytes>::new()))
+        });
+        http1::Builder::new()
+            .serve_connection(TokioIoToHyper1Io::new(server), svc)
+            .await
+            .unwrap();
+    });
+
+    let (send_request, conn) = client_http1::handshake(TokioIoToHyper1Io::new(client))
+        .await
+        .unwrap();
+    tokio::spawn(conn);
+    send_request
+}
+
+fn incoming(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let body = Bytes::from(vec![0; CHUNKS * CHUNK_SIZE]);
+
+    let mut group = c.benchmark_group("incoming");
+
+    let mut bench = |name: &str, mut send_request: SendRequest<Full<Bytes>>| {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                rt.block_on(async {
+                    let req = hyper_1::Request::new(Full::new(body.clone()));
+                    send_request.ready().await.unwrap();
+                    let res = send_request.send_request(req).await.unwrap();
+                    drain_1(res.into_body()).await;
+                })
+            })
+        });
+    };
+
+    bench(
+        "HttpBody1ToHttpBody04",
+        rt.block_on(connect(HttpBody1ToHttpBody04::new)),
+    );
+    bench(
+        "Hyper1IncomingAsHyper014Body",
+        rt.block_on(connect(Hyper1IncomingAsHyper014Body::new)),
+    );
+    bench(
+        "HttpBody1ToHttpBody04 + wrap_stream",
+        rt.block_on(connect(|body| {
+            hyper_014::Body::wrap_stream(data_stream(HttpBody1ToHttpBody04::new(body)))
+        })),
+    );
+    bench(
+        "into_hyper014_body",
+        rt.block_on(connect(|body| {
+            Hyper1IncomingAsHyper014Body::new(body).into_hyper014_body()
+        })),
+    );
+
+    group.finish();
+}
+
+fn hyper_014_body() -> hyper_014::Body {
+    let (mut tx, body) = hyper_014::Body::channel();
+    tokio::spawn(async move {
+        for _ in 0..CHUNKS {
+            if tx
+                .send_data(Bytes::from_static(&[0; CHUNK_SIZE]))
+                .await
+                .is_err()
+            {
+                break;
+            }
+        }
+    });
+    body
+}
+
+fn hyper_014(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let _guard = rt.enter();
+
+    let mut group = c.benchmark_group("hyper_014_body");
+
+    group.bench_function("HttpBody04ToHttpBody1", |b| {
+        b.iter(|| rt.block_on(drain_1(HttpBody04ToHttpBody1::new(hyper_014_body()))))
+    });
+    group.bench_function("Hyper014BodyAsHyper1Body", |b| {
+        b.iter(|| rt.block_on(drain_1(Hyper014BodyAsHyper1Body::new(hyper_014_body()))))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, incoming, hyper_014);
+criterion_main!(benches);
@@ -1,23 +1,51 @@
 use std::{
     pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     task::{Context, Poll},
 };
 
-use http::HeaderMap;
+use futures_core::Stream;
+use http_body_04::combinators::{BoxBody, UnsyncBoxBody};
 use http_body_1::Frame;
+use hyper_1::body::{Buf, Bytes};
 use pin_project_lite::pin_project;
+use tower::BoxError;
+
+use crate::{
+    hyper_http::{self, headers_from_hyper, headers_to_hyper},
+    Error,
+};
 
 // --- http-body 0.4 to http-body 1.0 ---
 
 pin_project! {
     /// Converts an [http-body 0.4 `Body`] to an [http-body 1.0 `Body`].
     ///
-    /// [http-body 0.4 `Body`]: https://docs.rs/http-body/latest/http_body/trait.Body.html
-    /// [http-body 1.0 `Body`]: https://docs.rs/http-body/1.0.0-rc.2/http_body/trait.Body.html
-    #[derive(Debug, Clone, Copy)]
-    pub struct HttpBody04ToHttpBody1<B> {
+    /// Trailers are converted from http 0.2's `HeaderMap` to http 1.0's `HeaderMap`.
+    ///
+    /// Errors from the inner body are passed through unchanged. Use
+    /// [`HttpBody04ToHttpBody1::strict`] to get errors of type [`Error`] instead.
+    ///
+    /// # Trailers
+    ///
+    /// [`poll_trailers`] is called once, after the inner body's data is exhausted, and its
+    /// result is yielded as the last frame. The inner body isn't polled again afterwards. Use
+    /// [`HttpBody04ToHttpBody1::with_trailers`] to add trailers to the body.
+    ///
+    /// [http-body 0.4 `Body`]: https://docs.rs/http-body/0.4/http_body/trait.Body.html
+    /// [http-body 1.0 `Body`]: https://docs.rs/http-body/1/http_body/trait.Body.html
+    /// [`poll_trailers`]: http_body_04::Body::poll_trailers
+    #[derive(Debug, Clone)]
+    pub struct HttpBody04ToHttpBody1<B, P = DropUnexpectedFrames> {
         #[pin]
         body: B,
+        attached_trailers: Option<hyper_http::HeaderMap>,
+        data_done: bool,
+        done: bool,
+        policy: P,
     }
 }
 
@@ -25,33 +53,115 @@ impl<B> HttpBody04ToHttpBody1<B> {
     /// Create a new `HttpBody04ToHttpBody1`.
     #[inline]
     pub fn new(body: B) -> Self {
-        Self { body }
+        Self {
+            body,
+            attached_trailers: None,
+            data_done: false,
+            done: false,
+            policy: DropUnexpectedFrames::new(),
+        }
+    }
+
+    /// Use the [`Strict`] policy such that errors are reported as [`Error`]s.
+    ///
+    /// This makes it possible to distinguish errors from the inner body from errors detected by
+    /// the adapter itself.
+    pub fn strict(self) -> HttpBody04ToHttpBody1<B, Strict> {
+        self.with_policy(Strict::new())
+    }
+
+    /// Use the [`Strict`] policy and limit the body to `limit` bytes of data.
+    ///
+    /// Reading more than `limit` bytes results in [`Error::LengthLimitExceeded`].
+    pub fn limit(self, limit: usize) -> HttpBody04ToHttpBody1<B, Strict> {
+        self.with_policy(Strict::new().limit(limit))
+    }
+}
+
+impl<B, P> HttpBody04ToHttpBody1<B, P> {
+    /// Send `trailers` after the inner body's data.
+    ///
+    /// If the inner body has trailers as well, the two are merged, with values from `trailers`
+    /// replacing values of the inner trailers with the same name.
+    pub fn with_trailers(mut self, trailers: hyper_http::HeaderMap) -> Self {
+        self.attached_trailers = merge_trailers(self.attached_trailers.take(), Some(trailers));
+        self
+    }
+
+    fn with_policy<T>(self, policy: T) -> HttpBody04ToHttpBody1<B, T> {
+        HttpBody04ToHttpBody1 {
+            body: self.body,
+            attached_trailers: self.attached_trailers,
+            data_done: self.data_done,
+            done: self.done,
+            policy,
+        }
+    }
+}
+
+impl<B> HttpBody04ToHttpBody1<B, Strict>
+where
+    B: http_body_04::Body,
+{
+    /// Check that the inner body yields exactly as many bytes as its size hint promised.
+    ///
+    /// If the inner body has an exact size hint, yielding more or fewer bytes of data results in
+    /// [`Error::SizeHintMismatch`]. Otherwise nothing is checked.
+    pub fn check_size_hint(mut self) -> Self {
+        if let Some(exact) = self.body.size_hint().exact() {
+            self.policy = self.policy.expect_length(exact);
+        }
+        self
     }
 }
 
-impl<B> http_body_1::Body for HttpBody04ToHttpBody1<B>
+impl<B, P> http_body_1::Body for HttpBody04ToHttpBody1<B, P>
 where
     B: http_body_04::Body,
+    P: FramePolicy<B::Data, B::Error>,
 {
     type Data = B::Data;
-    type Error = B::Error;
+    type Error = P::Error;
 
     fn poll_frame(
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
-        match self.as_mut().project().body.poll_data(cx) {
-            Poll::Ready(Some(Ok(buf))) => return Poll::Ready(Some(Ok(Frame::data(buf)))),
-            Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
-            Poll::Ready(None) => {}
-            Poll::Pending => return Poll::Pending,
+        let this = self.as_mut().project();
+
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        if !*this.data_done {
+            match ready!(this.body.poll_data(cx)) {
+                Some(Ok(buf)) => {
+                    if let Err(err) = this.policy.on_data(&buf) {
+                        return Poll::Ready(Some(Err(err)));
+                    }
+                    return Poll::Ready(Some(Ok(Frame::data(buf))));
+                }
+                Some(Err(err)) => return Poll::Ready(Some(Err(this.policy.map_error(err)))),
+                None => {
+                    *this.data_done = true;
+                    if let Err(err) = this.policy.on_end() {
+                        return Poll::Ready(Some(Err(err)));
+                    }
+                }
+            }
         }
 
-        match self.as_mut().project().body.poll_trailers(cx) {
-            Poll::Ready(Ok(Some(trailers))) => Poll::Ready(Some(Ok(Frame::trailers(trailers)))),
-            Poll::Ready(Ok(None)) => Poll::Ready(None),
-            Poll::Ready(Err(err)) => Poll::Ready(Some(Err(err))),
-            Poll::Pending => Poll::Pending,
+        let this = self.as_mut().project();
+        let trailers = ready!(this.body.poll_trailers(cx));
+        *this.done = true;
+        match trailers {
+            Ok(trailers) => merge_trailers(
+                trailers.map(headers_to_hyper),
+                this.attached_trailers.take(),
+            )
+            .map(|trailers| Ok(Frame::trailers(trailers)))
+            .into(),
+            Err(err) => Poll::Ready(Some(Err(this.policy.map_error(err)))),
         }
     }
 
@@ -67,7 +177,7 @@ where
 
     #[inline]
     fn is_end_stream(&self) -> bool {
-        self.body.is_end_stream()
+        self.done || (self.attached_trailers.is_none() && self.body.is_end_stream())
     }
 }
 
@@ -76,13 +186,41 @@ where
 pin_project! {
     /// Converts an [http-body 1.0 `Body`] to an [http-body 0.4 `Body`].
     ///
-    /// [http-body 0.4 `Body`]: https://docs.rs/http-body/latest/http_body/trait.Body.html
-    /// [http-body 1.0 `Body`]: https://docs.rs/http-body/1.0.0-rc.2/http_body/trait.Body.html
-    #[derive(Debug, Clone, Default)]
-    pub struct HttpBody1ToHttpBody04<B> {
+    /// Trailers are converted from http 1.0's `HeaderMap` to http 0.2's `HeaderMap`.
+    ///
+    /// # Unexpected frames
+    ///
+    /// http-body 0.4 only supports data followed by at most one set of trailers. Frames that
+    /// don't fit that model, such as data after trailers, multiple trailers, or frames that are
+    /// neither data nor trailers, are passed to a [`FramePolicy`]. By default they're dropped.
+    /// Use [`HttpBody1ToHttpBody04::on_unexpected_frame`] to customize that.
+    ///
+    /// Once trailers have been received, [`poll_trailers`] keeps polling the inner body until it
+    /// ends so any frames following the trailers are seen by the policy.
+    ///
+    /// # Trailers
+    ///
+    /// When [`poll_data`] receives trailers, the data stream ends and the trailers are kept until
+    /// [`poll_trailers`] is called. Use [`HttpBody1ToHttpBody04::take_trailers`] to get them
+    /// without converting them to http 0.2's `HeaderMap` and
+    /// [`HttpBody1ToHttpBody04::with_trailers`] to add trailers to the body.
+    ///
+    /// Use [`HttpBody1ToHttpBody04::strict`] to instead treat unexpected frames as errors and get
+    /// errors of type [`Error`].
+    ///
+    /// [http-body 0.4 `Body`]: https://docs.rs/http-body/0.4/http_body/trait.Body.html
+    /// [http-body 1.0 `Body`]: https://docs.rs/http-body/1/http_body/trait.Body.html
+    /// [`poll_data`]: http_body_04::Body::poll_data
+    /// [`poll_trailers`]: http_body_04::Body::poll_trailers
+    #[derive(Clone, Default)]
+    pub struct HttpBody1ToHttpBody04<B, P = DropUnexpectedFrames> {
         #[pin]
         body: B,
-        trailers: Option<HeaderMap>,
+        trailers: Option<hyper_http::HeaderMap>,
+        attached_trailers: Option<hyper_http::HeaderMap>,
+        trailers_received: bool,
+        done: bool,
+        policy: P,
     }
 }
 
@@ -93,40 +231,171 @@ impl<B> HttpBody1ToHttpBody04<B> {
         Self {
             body,
             trailers: None,
+            attached_trailers: None,
+            trailers_received: false,
+            done: false,
+            policy: DropUnexpectedFrames::new(),
         }
     }
+
+    /// Use the [`Strict`] policy such that unexpected frames result in an [`Error`].
+    ///
+    /// Errors from the inner body are reported as [`Error::Body`].
+    pub fn strict(self) -> HttpBody1ToHttpBody04<B, Strict> {
+        self.on_unexpected_frame(Strict::new())
+    }
+
+    /// Use the [`Strict`] policy and limit the body to `limit` bytes of data.
+    ///
+    /// Reading more than `limit` bytes results in [`Error::LengthLimitExceeded`].
+    pub fn limit(self, limit: usize) -> HttpBody1ToHttpBody04<B, Strict> {
+        self.on_unexpected_frame(Strict::new().limit(limit))
+    }
+}
+
+impl<B> HttpBody1ToHttpBody04<B, Strict>
+where
+    B: http_body_1::Body,
+{
+    /// Check that the inner body yields exactly as many bytes as its size hint promised.
+    ///
+    /// If the inner body has an exact size hint, yielding more or fewer bytes of data through
+    /// [`poll_data`] results in [`Error::SizeHintMismatch`]. Otherwise nothing is checked.
+    ///
+    /// [`poll_data`]: http_body_04::Body::poll_data
+    pub fn check_size_hint(mut self) -> Self {
+        if let Some(exact) = self.body.size_hint().exact() {
+            self.policy = self.policy.expect_length(exact);
+        }
+        self
+    }
+}
+
+impl<B, P> HttpBody1ToHttpBody04<B, P> {
+    /// Use `policy` to handle frames that cannot be represented by http-body 0.4.
+    ///
+    /// `policy` can be any [`FramePolicy`], such as [`Strict`] or a closure with the signature
+    /// `FnMut(UnexpectedFrame<B::Data>) -> Result<(), B::Error>`. Returning an error from the
+    /// closure will make the body yield that error.
+    pub fn on_unexpected_frame<T>(self, policy: T) -> HttpBody1ToHttpBody04<B, T> {
+        HttpBody1ToHttpBody04 {
+            body: self.body,
+            trailers: self.trailers,
+            attached_trailers: self.attached_trailers,
+            trailers_received: self.trailers_received,
+            done: self.done,
+            policy,
+        }
+    }
+
+    /// Send `trailers` after the inner body's data.
+    ///
+    /// If the inner body has trailers as well, the two are merged, with values from `trailers`
+    /// replacing values of the inner trailers with the same name.
+    pub fn with_trailers(mut self, trailers: hyper_http::HeaderMap) -> Self {
+        self.attached_trailers = merge_trailers(self.attached_trailers.take(), Some(trailers));
+        self
+    }
+
+    /// Take the trailers received from the inner body by [`poll_data`].
+    ///
+    /// Returns `None` if the data stream hasn't ended with trailers yet. Trailers added with
+    /// [`HttpBody1ToHttpBody04::with_trailers`] are merged into the result. Once taken,
+    /// [`poll_trailers`] no longer returns them.
+    ///
+    /// [`poll_data`]: http_body_04::Body::poll_data
+    /// [`poll_trailers`]: http_body_04::Body::poll_trailers
+    pub fn take_trailers(self: Pin<&mut Self>) -> Option<hyper_http::HeaderMap> {
+        let this = self.project();
+        let trailers = this.trailers.take()?;
+        merge_trailers(Some(trailers), this.attached_trailers.take())
+    }
+}
+
+impl<B> HttpBody1ToHttpBody04<B> {
+    /// The inner body and whether the data stream has ended.
+    ///
+    /// Trailers received or attached so far are dropped.
+    #[cfg(feature = "hyper-014")]
+    pub(crate) fn into_data_parts(self) -> (B, bool) {
+        (self.body, self.done || self.trailers_received)
+    }
 }
 
-impl<B> http_body_04::Body for HttpBody1ToHttpBody04<B>
+impl<B, P> std::fmt::Debug for HttpBody1ToHttpBody04<B, P>
+where
+    B: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpBody1ToHttpBody04")
+            .field("body", &self.body)
+            .field("trailers", &self.trailers)
+            .field("attached_trailers", &self.attached_trailers)
+            .field("trailers_received", &self.trailers_received)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+impl<B, P> http_body_04::Body for HttpBody1ToHttpBody04<B, P>
 where
     B: http_body_1::Body,
+    P: FramePolicy<B::Data, B::Error>,
 {
     type Data = B::Data;
-    type Error = B::Error;
+    type Error = P::Error;
 
     fn poll_data(
-        self: Pin<&mut Self>,
+        mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
-        let this = self.project();
-        match ready!(this.body.poll_frame(cx)) {
-            Some(Ok(frame)) => {
-                let frame = match frame.into_data() {
-                    Ok(data) => return Poll::Ready(Some(Ok(data))),
-                    Err(frame) => frame,
-                };
+        loop {
+            let this = self.as_mut().project();
 
-                match frame.into_trailers() {
-                    Ok(trailers) => {
-                        *this.trailers = Some(trailers);
+            if *this.done || *this.trailers_received {
+                return Poll::Ready(None);
+            }
+
+            match ready!(this.body.poll_frame(cx)) {
+                Some(Ok(frame)) => {
+                    let frame = match frame.into_data() {
+                        Ok(data) => {
+                            if let Err(err) = this.policy.on_data(&data) {
+                                return Poll::Ready(Some(Err(err)));
+                            }
+                            return Poll::Ready(Some(Ok(data)));
+                        }
+                        Err(frame) => frame,
+                    };
+
+                    match frame.into_trailers() {
+                        Ok(trailers) => {
+                            *this.trailers = Some(trailers);
+                            *this.trailers_received = true;
+                            if let Err(err) = this.policy.on_end() {
+                                return Poll::Ready(Some(Err(err)));
+                            }
+                            return Poll::Ready(None);
+                        }
+                        Err(frame) => {
+                            if let Err(err) = this
+                                .policy
+                                .on_unexpected_frame(UnexpectedFrame::Unknown(frame))
+                            {
+                                return Poll::Ready(Some(Err(err)));
+                            }
+                        }
                     }
-                    Err(_frame) => {}
                 }
-
-                Poll::Ready(None)
+                Some(Err(err)) => return Poll::Ready(Some(Err(this.policy.map_error(err)))),
+                None => {
+                    *this.done = true;
+                    if let Err(err) = this.policy.on_end() {
+                        return Poll::Ready(Some(Err(err)));
+                    }
+                    return Poll::Ready(None);
+                }
             }
-            Some(Err(err)) => Poll::Ready(Some(Err(err))),
-            None => Poll::Ready(None),
         }
     }
 
@@ -137,19 +406,45 @@ where
         loop {
             let this = self.as_mut().project();
 
-            if let Some(trailers) = this.trailers.take() {
-                break Poll::Ready(Ok(Some(trailers)));
+            if *this.done {
+                let trailers = merge_trailers(this.trailers.take(), this.attached_trailers.take());
+                return Poll::Ready(Ok(trailers.map(headers_from_hyper)));
             }
 
             match ready!(this.body.poll_frame(cx)) {
-                Some(Ok(frame)) => match frame.into_trailers() {
-                    Ok(trailers) => break Poll::Ready(Ok(Some(trailers))),
-                    // we might get a trailers frame on next poll
-                    // so loop and try again
-                    Err(_frame) => {}
-                },
-                Some(Err(err)) => break Poll::Ready(Err(err)),
-                None => break Poll::Ready(Ok(None)),
+                Some(Ok(frame)) => {
+                    let frame = match frame.into_data() {
+                        Ok(data) => {
+                            if *this.trailers_received {
+                                this.policy.on_unexpected_frame(
+                                    UnexpectedFrame::DataAfterTrailers(data),
+                                )?;
+                            }
+                            // data that wasn't consumed through `poll_data` is discarded
+                            continue;
+                        }
+                        Err(frame) => frame,
+                    };
+
+                    match frame.into_trailers() {
+                        Ok(trailers) => {
+                            if *this.trailers_received {
+                                this.policy.on_unexpected_frame(
+                                    UnexpectedFrame::TrailersAfterTrailers(trailers),
+                                )?;
+                            } else {
+                                *this.trailers = Some(trailers);
+                                *this.trailers_received = true;
+                            }
+                        }
+                        Err(frame) => {
+                            this.policy
+                                .on_unexpected_frame(UnexpectedFrame::Unknown(frame))?;
+                        }
+                    }
+                }
+                Some(Err(err)) => return Poll::Ready(Err(this.policy.map_error(err))),
+                None => *this.done = true,
             }
         }
     }
@@ -166,6 +461,511 @@ where
 
     #[inline]
     fn is_end_stream(&self) -> bool {
-        self.body.is_end_stream()
+        self.trailers.is_none()
+            && self.attached_trailers.is_none()
+            && (self.done || self.body.is_end_stream())
+    }
+}
+
+fn merge_trailers(
+    trailers: Option<hyper_http::HeaderMap>,
+    attached: Option<hyper_http::HeaderMap>,
+) -> Option<hyper_http::HeaderMap> {
+    match (trailers, attached) {
+        (Some(mut trailers), Some(attached)) => {
+            trailers.extend(attached);
+            Some(trailers)
+        }
+        (trailers, None) => trailers,
+        (None, attached) => attached,
+    }
+}
+
+// --- boxed bodies ---
+
+/// A type-erased [http-body 1.0 `Body`] converted from an [http-body 0.4 `Body`].
+///
+/// Errors are boxed so bodies with different error types have the same type. Create one with
+/// [`HttpBody04Ext::into_http_body1_boxed`].
+///
+/// [http-body 0.4 `Body`]: https://docs.rs/http-body/0.4/http_body/trait.Body.html
+/// [http-body 1.0 `Body`]: https://docs.rs/http-body/1/http_body/trait.Body.html
+/// [`HttpBody04Ext::into_http_body1_boxed`]: crate::HttpBody04Ext::into_http_body1_boxed
+pub type BoxBody04To1 = HttpBody04ToHttpBody1<BoxBody<Bytes, BoxError>>;
+
+/// Like [`BoxBody04To1`] but not `Sync`.
+///
+/// Create one with [`HttpBody04Ext::into_http_body1_boxed_unsync`].
+///
+/// [`HttpBody04Ext::into_http_body1_boxed_unsync`]: crate::HttpBody04Ext::into_http_body1_boxed_unsync
+pub type UnsyncBoxBody04To1 = HttpBody04ToHttpBody1<UnsyncBoxBody<Bytes, BoxError>>;
+
+/// A type-erased [http-body 0.4 `Body`] converted from an [http-body 1.0 `Body`].
+///
+/// Errors are boxed so bodies with different error types have the same type. Create one with
+/// [`HttpBody1Ext::into_http_body04_boxed`].
+///
+/// [http-body 0.4 `Body`]: https://docs.rs/http-body/0.4/http_body/trait.Body.html
+/// [http-body 1.0 `Body`]: https://docs.rs/http-body/1/http_body/trait.Body.html
+/// [`HttpBody1Ext::into_http_body04_boxed`]: crate::HttpBody1Ext::into_http_body04_boxed
+pub type BoxBody1To04 = BoxBody<Bytes, BoxError>;
+
+/// Like [`BoxBody1To04`] but not `Sync`.
+///
+/// Create one with [`HttpBody1Ext::into_http_body04_boxed_unsync`].
+///
+/// [`HttpBody1Ext::into_http_body04_boxed_unsync`]: crate::HttpBody1Ext::into_http_body04_boxed_unsync
+pub type UnsyncBoxBody1To04 = UnsyncBoxBody<Bytes, BoxError>;
+
+// --- streams ---
+
+pin_project! {
+    /// A body backed by a [`Stream`] of data.
+    ///
+    /// `StreamBody` implements both [http-body 0.4 `Body`] and [http-body 1.0 `Body`], so the
+    /// same body can be used on either side without choosing one of the direction adapters. It
+    /// never has trailers. Use [`FrameStreamBody`] to send trailers.
+    ///
+    /// [`Stream`]: https://docs.rs/futures-core/0.3/futures_core/stream/trait.Stream.html
+    /// [http-body 0.4 `Body`]: https://docs.rs/http-body/0.4/http_body/trait.Body.html
+    /// [http-body 1.0 `Body`]: https://docs.rs/http-body/1/http_body/trait.Body.html
+    #[derive(Debug, Clone, Copy)]
+    pub struct StreamBody<S> {
+        #[pin]
+        stream: S,
+    }
+}
+
+impl<S> StreamBody<S> {
+    /// Create a new `StreamBody`.
+    #[inline]
+    pub fn new(stream: S) -> Self {
+        Self { stream }
+    }
+
+    /// Get the inner stream back.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S, D, E> http_body_04::Body for StreamBody<S>
+where
+    S: Stream<Item = Result<D, E>>,
+    D: Buf,
+{
+    type Data = D;
+    type Error = E;
+
+    #[inline]
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        self.project().stream.poll_next(cx)
+    }
+
+    #[inline]
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        Poll::Ready(Ok(None))
+    }
+}
+
+impl<S, D, E> http_body_1::Body for StreamBody<S>
+where
+    S: Stream<Item = Result<D, E>>,
+    D: Buf,
+{
+    type Data = D;
+    type Error = E;
+
+    #[inline]
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        self.project()
+            .stream
+            .poll_next(cx)
+            .map(|item| item.map(|result| result.map(Frame::data)))
+    }
+}
+
+pin_project! {
+    /// A body backed by a [`Stream`] of http-body 1.0 [`Frame`]s.
+    ///
+    /// Like [`StreamBody`], `FrameStreamBody` implements both [http-body 0.4 `Body`] and
+    /// [http-body 1.0 `Body`], but the stream can also yield trailers.
+    ///
+    /// When used as an http-body 0.4 `Body`, the data stream ends at the first trailers frame and
+    /// the trailers are returned from [`poll_trailers`], converted to http 0.2's `HeaderMap`.
+    /// Anything the stream yields after the trailers is ignored, as is data that hasn't been
+    /// consumed through [`poll_data`] when `poll_trailers` is called.
+    ///
+    /// [`Stream`]: https://docs.rs/futures-core/0.3/futures_core/stream/trait.Stream.html
+    /// [http-body 0.4 `Body`]: https://docs.rs/http-body/0.4/http_body/trait.Body.html
+    /// [http-body 1.0 `Body`]: https://docs.rs/http-body/1/http_body/trait.Body.html
+    /// [`poll_data`]: http_body_04::Body::poll_data
+    /// [`poll_trailers`]: http_body_04::Body::poll_trailers
+    #[derive(Debug)]
+    pub struct FrameStreamBody<S> {
+        #[pin]
+        stream: S,
+        trailers: Option<hyper_http::HeaderMap>,
+        done: bool,
+    }
+}
+
+impl<S> FrameStreamBody<S> {
+    /// Create a new `FrameStreamBody`.
+    #[inline]
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            trailers: None,
+            done: false,
+        }
+    }
+
+    /// Get the inner stream back.
+    ///
+    /// Trailers received through [`poll_data`] but not yet taken with [`poll_trailers`] are
+    /// dropped.
+    ///
+    /// [`poll_data`]: http_body_04::Body::poll_data
+    /// [`poll_trailers`]: http_body_04::Body::poll_trailers
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S, D, E> http_body_04::Body for FrameStreamBody<S>
+where
+    S: Stream<Item = Result<Frame<D>, E>>,
+    D: Buf,
+{
+    type Data = D;
+    type Error = E;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let mut this = self.project();
+        loop {
+            if *this.done || this.trailers.is_some() {
+                return Poll::Ready(None);
+            }
+
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(Ok(frame)) => match frame.into_data() {
+                    Ok(data) => return Poll::Ready(Some(Ok(data))),
+                    Err(frame) => {
+                        // frames that are neither data nor trailers are skipped
+                        if let Ok(trailers) = frame.into_trailers() {
+                            *this.trailers = Some(trailers);
+                        }
+                    }
+                },
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => *this.done = true,
+            }
+        }
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        let mut this = self.project();
+        loop {
+            if let Some(trailers) = this.trailers.take() {
+                *this.done = true;
+                return Poll::Ready(Ok(Some(headers_from_hyper(trailers))));
+            }
+
+            if *this.done {
+                return Poll::Ready(Ok(None));
+            }
+
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(Ok(frame)) => {
+                    if let Ok(trailers) = frame.into_trailers() {
+                        *this.trailers = Some(trailers);
+                    }
+                }
+                Some(Err(err)) => return Poll::Ready(Err(err)),
+                None => *this.done = true,
+            }
+        }
+    }
+
+    #[inline]
+    fn is_end_stream(&self) -> bool {
+        self.done && self.trailers.is_none()
+    }
+}
+
+impl<S, D, E> http_body_1::Body for FrameStreamBody<S>
+where
+    S: Stream<Item = Result<Frame<D>, E>>,
+    D: Buf,
+{
+    type Data = D;
+    type Error = E;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        if let Some(trailers) = this.trailers.take() {
+            *this.done = true;
+            return Poll::Ready(Some(Ok(Frame::trailers(trailers))));
+        }
+
+        if *this.done {
+            return Poll::Ready(None);
+        }
+
+        let item = ready!(this.stream.poll_next(cx));
+        if item.is_none() {
+            *this.done = true;
+        }
+        Poll::Ready(item)
+    }
+
+    #[inline]
+    fn is_end_stream(&self) -> bool {
+        self.done && self.trailers.is_none()
+    }
+}
+
+/// A frame that cannot be represented by an [http-body 0.4 `Body`].
+///
+/// See [`FramePolicy`] for more details.
+///
+/// [http-body 0.4 `Body`]: https://docs.rs/http-body/0.4/http_body/trait.Body.html
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum UnexpectedFrame<D> {
+    /// A data frame received after a trailers frame.
+    DataAfterTrailers(D),
+    /// A trailers frame received after another trailers frame.
+    TrailersAfterTrailers(hyper_http::HeaderMap),
+    /// A frame that is neither data nor trailers.
+    Unknown(Frame<D>),
+}
+
+/// Policy used by the body adapters for handling errors and frames that cannot be converted.
+///
+/// `D` is the data type and `E` the error type of the inner body.
+///
+/// This trait is implemented for [`DropUnexpectedFrames`], which is used by default, for
+/// [`Strict`], and for closures with the signature `FnMut(UnexpectedFrame<D>) -> Result<(), E>`.
+///
+/// See [`HttpBody1ToHttpBody04::on_unexpected_frame`].
+pub trait FramePolicy<D, E> {
+    /// The error type of the converted body.
+    type Error;
+
+    /// Convert an error from the inner body.
+    fn map_error(&mut self, err: E) -> Self::Error;
+
+    /// Inspect a data frame before it's yielded by the converted body.
+    ///
+    /// Returning an error makes the body yield that error instead of the data. The default
+    /// implementation accepts all data.
+    #[inline]
+    fn on_data(&mut self, data: &D) -> Result<(), Self::Error> {
+        let _ = data;
+        Ok(())
+    }
+
+    /// Called when the inner body has no more data.
+    ///
+    /// Returning an error makes the body yield that error before ending. The default
+    /// implementation does nothing.
+    #[inline]
+    fn on_end(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Handle a frame that cannot be represented by an [http-body 0.4 `Body`].
+    ///
+    /// Returning `Ok(())` drops the frame and continues with the next one. Returning an error
+    /// makes the body yield that error.
+    ///
+    /// This is never called by [`HttpBody04ToHttpBody1`] since all http-body 0.4 frames can be
+    /// represented by http-body 1.0.
+    ///
+    /// [http-body 0.4 `Body`]: https://docs.rs/http-body/0.4/http_body/trait.Body.html
+    fn on_unexpected_frame(&mut self, frame: UnexpectedFrame<D>) -> Result<(), Self::Error>;
+}
+
+impl<F, D, E> FramePolicy<D, E> for F
+where
+    F: FnMut(UnexpectedFrame<D>) -> Result<(), E>,
+{
+    type Error = E;
+
+    #[inline]
+    fn map_error(&mut self, err: E) -> Self::Error {
+        err
+    }
+
+    #[inline]
+    fn on_unexpected_frame(&mut self, frame: UnexpectedFrame<D>) -> Result<(), E> {
+        self(frame)
+    }
+}
+
+/// A [`FramePolicy`] that silently drops unexpected frames.
+///
+/// This is the default used by [`HttpBody1ToHttpBody04`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DropUnexpectedFrames {
+    _priv: (),
+}
+
+impl DropUnexpectedFrames {
+    /// Create a new `DropUnexpectedFrames`.
+    #[inline]
+    pub fn new() -> Self {
+        Self { _priv: () }
+    }
+}
+
+impl<D, E> FramePolicy<D, E> for DropUnexpectedFrames {
+    type Error = E;
+
+    #[inline]
+    fn map_error(&mut self, err: E) -> Self::Error {
+        err
+    }
+
+    #[inline]
+    fn on_unexpected_frame(&mut self, _frame: UnexpectedFrame<D>) -> Result<(), E> {
+        Ok(())
+    }
+}
+
+/// A [`FramePolicy`] that reports protocol violations as [`Error`]s.
+///
+/// Errors from the inner body are wrapped in [`Error::Body`] and unexpected frames are turned
+/// into the corresponding [`Error`] variant.
+///
+/// A limit on the number of bytes of data can be set with [`Strict::limit`] and the expected
+/// number of bytes with [`Strict::expect_length`].
+///
+/// See [`HttpBody04ToHttpBody1::strict`] and [`HttpBody1ToHttpBody04::strict`].
+#[derive(Clone, Debug, Default)]
+pub struct Strict {
+    limit: Option<u64>,
+    expected: Option<u64>,
+    read: u64,
+    limit_exceeded: Option<Arc<AtomicBool>>,
+}
+
+impl Strict {
+    /// Create a new `Strict`.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            limit: None,
+            expected: None,
+            read: 0,
+            limit_exceeded: None,
+        }
+    }
+
+    /// Set `flag` once the data exceeds the limit, so whoever created the body can tell that the
+    /// limit was hit without seeing the error.
+    #[inline]
+    pub(crate) fn notify_limit_exceeded(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.limit_exceeded = Some(flag);
+        self
+    }
+
+    /// Limit the body to `limit` bytes of data.
+    ///
+    /// Data exceeding the limit results in [`Error::LengthLimitExceeded`].
+    #[inline]
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit as u64);
+        self
+    }
+
+    /// Expect the body to contain exactly `len` bytes of data.
+    ///
+    /// More or less data results in [`Error::SizeHintMismatch`]. This is usually set from the
+    /// inner body's size hint with [`HttpBody04ToHttpBody1::check_size_hint`] or
+    /// [`HttpBody1ToHttpBody04::check_size_hint`].
+    #[inline]
+    pub fn expect_length(mut self, len: u64) -> Self {
+        self.expected = Some(len);
+        self
+    }
+}
+
+impl<D, E> FramePolicy<D, E> for Strict
+where
+    D: Buf,
+    E: Into<BoxError>,
+{
+    type Error = Error;
+
+    #[inline]
+    fn map_error(&mut self, err: E) -> Self::Error {
+        Error::Body(err.into())
+    }
+
+    fn on_data(&mut self, data: &D) -> Result<(), Self::Error> {
+        self.read = self.read.saturating_add(data.remaining() as u64);
+
+        if let Some(expected) = self.expected {
+            if self.read > expected {
+                // only report the mismatch once
+                self.expected = None;
+                return Err(Error::SizeHintMismatch {
+                    expected,
+                    actual: self.read,
+                });
+            }
+        }
+
+        match self.limit {
+            Some(limit) if self.read > limit => {
+                if let Some(flag) = &self.limit_exceeded {
+                    flag.store(true, Ordering::Relaxed);
+                }
+                Err(Error::LengthLimitExceeded)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn on_end(&mut self) -> Result<(), Self::Error> {
+        match self.expected.take() {
+            Some(expected) if self.read != expected => Err(Error::SizeHintMismatch {
+                expected,
+                actual: self.read,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn on_unexpected_frame(&mut self, frame: UnexpectedFrame<D>) -> Result<(), Self::Error> {
+        let err = match frame {
+            UnexpectedFrame::DataAfterTrailers(_) => Error::DataAfterTrailers,
+            UnexpectedFrame::TrailersAfterTrailers(_) => Error::MultipleTrailers,
+            UnexpectedFrame::Unknown(_) => Error::UnknownFrame,
+        };
+        Err(err)
     }
 }
@@ -0,0 +1,105 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use http::Response;
+use http_body_04::{combinators::UnsyncBoxBody, Body as _};
+use hyper_1::body::Bytes;
+use pin_project_lite::pin_project;
+use tower::BoxError;
+
+/// A [tower-service 0.3 `Service`] that boxes the response bodies of the inner service.
+///
+/// The response body is always `UnsyncBoxBody<Bytes, BoxError>`, regardless of the inner
+/// service's response body type. Errors from the response body are converted into [`BoxError`].
+///
+/// See also [`TowerService03HttpServiceAsHyper1HttpService::box_response_body`].
+///
+/// [tower-service 0.3 `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
+/// [`TowerService03HttpServiceAsHyper1HttpService::box_response_body`]: crate::TowerService03HttpServiceAsHyper1HttpService::box_response_body
+#[derive(Clone, Copy, Debug)]
+pub struct BoxResponseBody<S> {
+    service: S,
+}
+
+impl<S> BoxResponseBody<S> {
+    /// Create a new `BoxResponseBody`.
+    #[inline]
+    pub fn new(service: S) -> Self {
+        Self { service }
+    }
+}
+
+impl<S, R, B> tower_service_03::Service<R> for BoxResponseBody<S>
+where
+    S: tower_service_03::Service<R, Response = Response<B>>,
+    B: http_body_04::Body<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    type Response = Response<UnsyncBoxBody<Bytes, BoxError>>;
+    type Error = S::Error;
+    type Future = BoxResponseBodyFuture<S::Future>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    #[inline]
+    fn call(&mut self, req: R) -> Self::Future {
+        BoxResponseBodyFuture {
+            future: self.service.call(req),
+        }
+    }
+}
+
+pin_project! {
+    /// Response future for [`BoxResponseBody`].
+    #[derive(Debug)]
+    pub struct BoxResponseBodyFuture<F> {
+        #[pin]
+        future: F,
+    }
+}
+
+impl<F, B, E> Future for BoxResponseBodyFuture<F>
+where
+    F: Future<Output = Result<Response<B>, E>>,
+    B: http_body_04::Body<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    type Output = Result<Response<UnsyncBoxBody<Bytes, BoxError>>, E>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let res = ready!(self.project().future.poll(cx))?;
+        Poll::Ready(Ok(res.map(|body| body.map_err(Into::into).boxed_unsync())))
+    }
+}
+
+/// A [tower-layer 0.3 `Layer`] that produces [`BoxResponseBody`]s.
+///
+/// [tower-layer 0.3 `Layer`]: https://docs.rs/tower-layer/latest/tower_layer/trait.Layer.html
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BoxResponseBodyLayer {
+    _priv: (),
+}
+
+impl BoxResponseBodyLayer {
+    /// Create a new `BoxResponseBodyLayer`.
+    #[inline]
+    pub fn new() -> Self {
+        Self { _priv: () }
+    }
+}
+
+impl<S> tower_layer_03::Layer<S> for BoxResponseBodyLayer {
+    type Service = BoxResponseBody<S>;
+
+    #[inline]
+    fn layer(&self, inner: S) -> Self::Service {
+        BoxResponseBody::new(inner)
+    }
+}
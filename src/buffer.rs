@@ -0,0 +1,119 @@
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project_lite::pin_project;
+use tower::{
+    buffer::{future::ResponseFuture, Buffer},
+    BoxError,
+};
+
+/// A [tower-service 0.3 `Service`] that drives backpressure on a single instance of the inner
+/// service.
+///
+/// Requests are sent to a worker task that calls `poll_ready` on the inner service before
+/// dispatching each of them, so all clones of `Buffered` share the inner service's readiness.
+/// Errors are boxed.
+///
+/// Created with [`TowerService03ServiceAsHyper1Service::buffered`] and
+/// [`TowerService03HttpServiceAsHyper1HttpService::buffered`].
+///
+/// [tower-service 0.3 `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
+/// [`TowerService03ServiceAsHyper1Service::buffered`]: crate::TowerService03ServiceAsHyper1Service::buffered
+/// [`TowerService03HttpServiceAsHyper1HttpService::buffered`]: crate::TowerService03HttpServiceAsHyper1HttpService::buffered
+pub struct Buffered<S, R>
+where
+    S: tower_service_03::Service<R>,
+{
+    inner: Buffer<S, R>,
+}
+
+impl<S, R> Buffered<S, R>
+where
+    S: tower_service_03::Service<R>,
+    S::Error: Into<BoxError>,
+{
+    pub(crate) fn new(inner: S, bound: usize) -> Self
+    where
+        S: Send + 'static,
+        S::Future: Send,
+        S::Error: Send + Sync,
+        R: Send + 'static,
+    {
+        Self {
+            inner: Buffer::new(inner, bound),
+        }
+    }
+}
+
+impl<S, R> Clone for Buffered<S, R>
+where
+    S: tower_service_03::Service<R>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<S, R> fmt::Debug for Buffered<S, R>
+where
+    S: tower_service_03::Service<R>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Buffered").finish_non_exhaustive()
+    }
+}
+
+impl<S, R> tower_service_03::Service<R> for Buffered<S, R>
+where
+    S: tower_service_03::Service<R>,
+    S::Error: Into<BoxError>,
+{
+    type Response = S::Response;
+    type Error = BoxError;
+    type Future = BufferedFuture<S::Future>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    #[inline]
+    fn call(&mut self, req: R) -> Self::Future {
+        BufferedFuture {
+            future: self.inner.call(req),
+        }
+    }
+}
+
+pin_project! {
+    /// Response future for [`Buffered`].
+    pub struct BufferedFuture<F> {
+        #[pin]
+        future: ResponseFuture<F>,
+    }
+}
+
+impl<F> fmt::Debug for BufferedFuture<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferedFuture").finish_non_exhaustive()
+    }
+}
+
+impl<F, T, E> Future for BufferedFuture<F>
+where
+    F: Future<Output = Result<T, E>>,
+    E: Into<BoxError>,
+{
+    type Output = Result<T, BoxError>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().future.poll(cx)
+    }
+}
@@ -0,0 +1,172 @@
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use http::{Request, Response};
+use hyper_1::body::Incoming;
+
+use crate::{
+    hyper_http::{self, request_to_hyper, response_from_hyper},
+    HttpBody04ToHttpBody1, HttpBody1ToHttpBody04,
+};
+
+/// Converts a hyper 1.0 `SendRequest` to a [tower-service 0.3 HTTP `Service`].
+///
+/// hyper 1.0's [`http1::SendRequest`] and [`http2::SendRequest`] don't implement hyper 1.0's
+/// `Service` trait, so they cannot be used with [`Hyper1HttpServiceAsTowerService03HttpService`].
+/// This adapter instead calls them directly:
+///
+/// - `poll_ready` is forwarded to `SendRequest::poll_ready`, so tower middleware observes when
+///   the connection is busy or closed.
+/// - Request bodies are converted with [`HttpBody04ToHttpBody1`] and response bodies with
+///   [`HttpBody1ToHttpBody04`].
+/// - Requests and responses are converted between http 0.2 and http 1.0 as described in the
+///   [`convert`](crate::convert) module.
+///
+/// This allows using tower client middleware, such as retries, timeouts, or tracing, with
+/// hyper 1.0 client connections.
+///
+/// # Example
+///
+/// ```no_run
+/// # use hyper_1 as hyper;
+/// use http::Request;
+/// use hyper::client::conn::http1;
+/// use tokio::net::TcpStream;
+/// use tower::{ServiceBuilder, ServiceExt};
+/// use tower_hyper_http_body_compat::{
+///     Hyper1SendRequestAsTowerService03HttpService, TokioIoToHyper1Io,
+/// };
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///     let tcp_stream = TcpStream::connect("127.0.0.1:8080").await?;
+///     let (send_request, conn) = http1::handshake(TokioIoToHyper1Io::new(tcp_stream)).await?;
+///     tokio::spawn(conn);
+///
+///     let client = ServiceBuilder::new()
+///         .timeout(std::time::Duration::from_secs(10))
+///         .service(Hyper1SendRequestAsTowerService03HttpService::new(send_request));
+///
+///     let req = Request::builder()
+///         .uri("/")
+///         .body(hyper_014::Body::empty())?;
+///     let res = client.oneshot(req).await?;
+///     let body = hyper_014::body::to_bytes(res).await?;
+///     println!("{:?}", body);
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// [tower-service 0.3 HTTP `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
+/// [`http1::SendRequest`]: hyper_1::client::conn::http1::SendRequest
+/// [`http2::SendRequest`]: hyper_1::client::conn::http2::SendRequest
+/// [`Hyper1HttpServiceAsTowerService03HttpService`]: crate::Hyper1HttpServiceAsTowerService03HttpService
+#[derive(Clone, Debug)]
+pub struct Hyper1SendRequestAsTowerService03HttpService<S> {
+    send_request: S,
+}
+
+impl<S> Hyper1SendRequestAsTowerService03HttpService<S> {
+    /// Create a new `Hyper1SendRequestAsTowerService03HttpService`.
+    #[inline]
+    pub fn new(send_request: S) -> Self {
+        Self { send_request }
+    }
+
+    /// Get a reference to the inner `SendRequest`.
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.send_request
+    }
+
+    /// Get a mutable reference to the inner `SendRequest`.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.send_request
+    }
+
+    /// Consume `self`, returning the inner `SendRequest`.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.send_request
+    }
+}
+
+#[cfg(feature = "http1")]
+impl<B> tower_service_03::Service<Request<B>>
+    for Hyper1SendRequestAsTowerService03HttpService<
+        hyper_1::client::conn::http1::SendRequest<HttpBody04ToHttpBody1<B>>,
+    >
+where
+    B: http_body_04::Body + Send + 'static,
+{
+    type Response = Response<HttpBody1ToHttpBody04<Incoming>>;
+    type Error = hyper_1::Error;
+    type Future = Hyper1SendRequestAsTowerService03HttpServiceFuture;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.send_request.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let req = request_to_hyper(req.map(HttpBody04ToHttpBody1::new));
+        Hyper1SendRequestAsTowerService03HttpServiceFuture {
+            future: Box::pin(self.send_request.send_request(req)),
+        }
+    }
+}
+
+#[cfg(feature = "http2")]
+impl<B> tower_service_03::Service<Request<B>>
+    for Hyper1SendRequestAsTowerService03HttpService<
+        hyper_1::client::conn::http2::SendRequest<HttpBody04ToHttpBody1<B>>,
+    >
+where
+    B: http_body_04::Body + Send + 'static,
+{
+    type Response = Response<HttpBody1ToHttpBody04<Incoming>>;
+    type Error = hyper_1::Error;
+    type Future = Hyper1SendRequestAsTowerService03HttpServiceFuture;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.send_request.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let req = request_to_hyper(req.map(HttpBody04ToHttpBody1::new));
+        Hyper1SendRequestAsTowerService03HttpServiceFuture {
+            future: Box::pin(self.send_request.send_request(req)),
+        }
+    }
+}
+
+/// Response future for [`Hyper1SendRequestAsTowerService03HttpService`].
+pub struct Hyper1SendRequestAsTowerService03HttpServiceFuture {
+    future: Pin<
+        Box<dyn Future<Output = Result<hyper_http::Response<Incoming>, hyper_1::Error>> + Send>,
+    >,
+}
+
+impl fmt::Debug for Hyper1SendRequestAsTowerService03HttpServiceFuture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hyper1SendRequestAsTowerService03HttpServiceFuture")
+            .finish()
+    }
+}
+
+impl Future for Hyper1SendRequestAsTowerService03HttpServiceFuture {
+    type Output = Result<Response<HttpBody1ToHttpBody04<Incoming>>, hyper_1::Error>;
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let res = ready!(self.future.as_mut().poll(cx))?;
+        Poll::Ready(Ok(response_from_hyper(res).map(HttpBody1ToHttpBody04::new)))
+    }
+}
@@ -0,0 +1,153 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project_lite::pin_project;
+
+use crate::{
+    hyper_http::{self, uri_from_hyper, uri_to_hyper},
+    Hyper1IoToTokioIo, TokioIoToHyper1Io,
+};
+
+// --- hyper 0.14 to hyper 1.0 ---
+
+/// Converts a [hyper 0.14 connector] to a connector that returns IO objects implementing
+/// [hyper 1.0's `rt::Read` and `rt::Write`].
+///
+/// hyper 0.14 connectors are tower-service 0.3 `Service<Uri>`s returning IO objects that
+/// implement tokio's IO traits. This adapter accepts http 1.0's `Uri`, converts it to http 0.2's
+/// `Uri`, and wraps the returned IO objects in [`TokioIoToHyper1Io`] so they can be passed to
+/// hyper 1.0's client connections.
+///
+/// [`TokioIoToHyper1Io`] implements hyper 0.14's [`Connection`] trait by delegating to the inner
+/// IO object, so the [`Connected`] metadata, such as whether the connection is proxied, whether
+/// HTTP/2 was negotiated with ALPN, and any extras, is preserved.
+///
+/// [hyper 0.14 connector]: https://docs.rs/hyper/0.14/hyper/client/connect/index.html
+/// [hyper 1.0's `rt::Read` and `rt::Write`]: https://docs.rs/hyper/1/hyper/rt/index.html
+/// [`Connection`]: hyper_014::client::connect::Connection
+/// [`Connected`]: hyper_014::client::connect::Connected
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Hyper014ConnectorAsHyper1Connector<C>(C);
+
+impl<C> Hyper014ConnectorAsHyper1Connector<C> {
+    /// Create a new `Hyper014ConnectorAsHyper1Connector`.
+    #[inline]
+    pub fn new(inner: C) -> Self {
+        Self(inner)
+    }
+}
+
+impl<C> tower_service_03::Service<hyper_http::Uri> for Hyper014ConnectorAsHyper1Connector<C>
+where
+    C: tower_service_03::Service<http::Uri>,
+{
+    type Response = TokioIoToHyper1Io<C::Response>;
+    type Error = C::Error;
+    type Future = Hyper014ConnectorAsHyper1ConnectorFuture<C::Future>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_ready(cx)
+    }
+
+    #[inline]
+    fn call(&mut self, dst: hyper_http::Uri) -> Self::Future {
+        Hyper014ConnectorAsHyper1ConnectorFuture {
+            future: self.0.call(uri_from_hyper(dst)),
+        }
+    }
+}
+
+pin_project! {
+    /// Response future for [`Hyper014ConnectorAsHyper1Connector`].
+    #[derive(Debug)]
+    pub struct Hyper014ConnectorAsHyper1ConnectorFuture<F> {
+        #[pin]
+        future: F,
+    }
+}
+
+impl<F, T, E> Future for Hyper014ConnectorAsHyper1ConnectorFuture<F>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    type Output = Result<TokioIoToHyper1Io<T>, E>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let io = ready!(self.project().future.poll(cx))?;
+        Poll::Ready(Ok(TokioIoToHyper1Io::new(io)))
+    }
+}
+
+// --- hyper 1.0 to hyper 0.14 ---
+
+/// Converts a connector that returns IO objects implementing [hyper 1.0's `rt::Read` and
+/// `rt::Write`] to a [hyper 0.14 connector].
+///
+/// The http 0.2 `Uri` passed by hyper 0.14 is converted to http 1.0's `Uri` and the returned IO
+/// objects are wrapped in [`Hyper1IoToTokioIo`] so they implement tokio's IO traits. hyper 0.14's
+/// `Client` also requires them to implement hyper 0.14's [`Connection`] trait, which
+/// [`Hyper1IoToTokioIo`] does by delegating to the inner IO object. That means the
+/// inner IO object must implement [`Connection`] as well, which is the case for IO objects
+/// returned by [`Hyper014ConnectorAsHyper1Connector`].
+///
+/// [hyper 0.14 connector]: https://docs.rs/hyper/0.14/hyper/client/connect/index.html
+/// [hyper 1.0's `rt::Read` and `rt::Write`]: https://docs.rs/hyper/1/hyper/rt/index.html
+/// [`Connection`]: hyper_014::client::connect::Connection
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Hyper1ConnectorAsHyper014Connector<C>(C);
+
+impl<C> Hyper1ConnectorAsHyper014Connector<C> {
+    /// Create a new `Hyper1ConnectorAsHyper014Connector`.
+    #[inline]
+    pub fn new(inner: C) -> Self {
+        Self(inner)
+    }
+}
+
+impl<C> tower_service_03::Service<http::Uri> for Hyper1ConnectorAsHyper014Connector<C>
+where
+    C: tower_service_03::Service<hyper_http::Uri>,
+{
+    type Response = Hyper1IoToTokioIo<C::Response>;
+    type Error = C::Error;
+    type Future = Hyper1ConnectorAsHyper014ConnectorFuture<C::Future>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_ready(cx)
+    }
+
+    #[inline]
+    fn call(&mut self, dst: http::Uri) -> Self::Future {
+        Hyper1ConnectorAsHyper014ConnectorFuture {
+            future: self.0.call(uri_to_hyper(dst)),
+        }
+    }
+}
+
+pin_project! {
+    /// Response future for [`Hyper1ConnectorAsHyper014Connector`].
+    #[derive(Debug)]
+    pub struct Hyper1ConnectorAsHyper014ConnectorFuture<F> {
+        #[pin]
+        future: F,
+    }
+}
+
+impl<F, T, E> Future for Hyper1ConnectorAsHyper014ConnectorFuture<F>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    type Output = Result<Hyper1IoToTokioIo<T>, E>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let io = ready!(self.project().future.poll(cx))?;
+        Poll::Ready(Ok(Hyper1IoToTokioIo::new(io)))
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    net::SocketAddr,
+    task::{Context, Poll},
+};
+
+use http::Request;
+
+/// The addresses of the connection a request was received on.
+///
+/// hyper 0.14's `AddrStream` provided these to the services created by its `Server`. hyper 1.0
+/// leaves accepting connections to the caller, so they have to be attached to each connection's
+/// service with [`AddConnectionInfo`] instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ConnectionInfo {
+    remote_addr: SocketAddr,
+    local_addr: SocketAddr,
+}
+
+impl ConnectionInfo {
+    /// Create a new `ConnectionInfo`.
+    #[inline]
+    pub fn new(remote_addr: SocketAddr, local_addr: SocketAddr) -> Self {
+        Self {
+            remote_addr,
+            local_addr,
+        }
+    }
+
+    /// The address of the peer.
+    #[inline]
+    pub fn remote_addr(&self) -> SocketAddr {
+        self.remote_addr
+    }
+
+    /// The local address the connection was accepted on.
+    #[inline]
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+/// A [tower-service 0.3 `Service`] that inserts per-connection information into the extensions
+/// of every request.
+///
+/// Create one `AddConnectionInfo` for each accepted connection with information about that
+/// connection, such as its [`ConnectionInfo`] or TLS session. `info` is cloned into the
+/// extensions of every request received on the connection, where middleware that used to rely
+/// on hyper 0.14's `AddrStream` can find it. For example, passing axum's
+/// `ConnectInfo(remote_addr)` makes the `ConnectInfo` extractor work without
+/// `into_make_service_with_connect_info`.
+///
+/// Wrap `info` in a tuple or struct to insert several values, or add several
+/// `AddConnectionInfo`s.
+///
+/// See also [`TowerService03HttpServiceAsHyper1HttpService::connection_info`].
+///
+/// [tower-service 0.3 `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
+/// [`TowerService03HttpServiceAsHyper1HttpService::connection_info`]: crate::TowerService03HttpServiceAsHyper1HttpService::connection_info
+#[derive(Clone, Copy, Debug)]
+pub struct AddConnectionInfo<S, T> {
+    service: S,
+    info: T,
+}
+
+impl<S, T> AddConnectionInfo<S, T> {
+    /// Create a new `AddConnectionInfo`.
+    #[inline]
+    pub fn new(service: S, info: T) -> Self {
+        Self { service, info }
+    }
+}
+
+impl<S, T, B> tower_service_03::Service<Request<B>> for AddConnectionInfo<S, T>
+where
+    S: tower_service_03::Service<Request<B>>,
+    T: Clone + Send + Sync + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    #[inline]
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        req.extensions_mut().insert(self.info.clone());
+        self.service.call(req)
+    }
+}
+
+/// A [tower-layer 0.3 `Layer`] that produces [`AddConnectionInfo`]s.
+///
+/// [tower-layer 0.3 `Layer`]: https://docs.rs/tower-layer/latest/tower_layer/trait.Layer.html
+#[derive(Clone, Copy, Debug)]
+pub struct AddConnectionInfoLayer<T> {
+    info: T,
+}
+
+impl<T> AddConnectionInfoLayer<T> {
+    /// Create a new `AddConnectionInfoLayer`.
+    #[inline]
+    pub fn new(info: T) -> Self {
+        Self { info }
+    }
+}
+
+impl<S, T> tower_layer_03::Layer<S> for AddConnectionInfoLayer<T>
+where
+    T: Clone,
+{
+    type Service = AddConnectionInfo<S, T>;
+
+    #[inline]
+    fn layer(&self, inner: S) -> Self::Service {
+        AddConnectionInfo::new(inner, self.info.clone())
+    }
+}
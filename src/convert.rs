@@ -0,0 +1,370 @@
+//! Conversions between [http 0.2] and [http 1.0] types.
+//!
+//! hyper 0.14, http-body 0.4, tower-http 0.4, and axum 0.6 use http 0.2, while hyper 1.0 and
+//! http-body 1.0 use http 1.0. The types from the two versions are distinct, so they have to be
+//! converted at the boundary.
+//!
+//! Request and response heads, header maps (including trailers), methods, status codes, URIs,
+//! and versions are converted exactly.
+//!
+//! Extensions can only be converted on a best-effort basis since neither version provides a way
+//! to iterate over the values. Instead the extensions from one version are stored as a single
+//! value in the extensions of the other version, wrapped in [`Http1Extensions`] or
+//! [`Http02Extensions`]. Converting back unwraps them again, so extensions survive a round trip.
+//!
+//! To convert entire services use [`Http02ServiceAsHttp1Service`] and
+//! [`Http1ServiceAsHttp02Service`].
+//!
+//! [http 0.2]: https://docs.rs/http/0.2
+//! [http 1.0]: https://docs.rs/http/1.0
+//! [`Http02ServiceAsHttp1Service`]: crate::Http02ServiceAsHttp1Service
+//! [`Http1ServiceAsHttp02Service`]: crate::Http1ServiceAsHttp02Service
+
+use std::{fmt, sync::Arc};
+
+/// Convert an http 0.2 `Request` to an http 1.0 `Request`.
+pub fn request_to_http1<B>(req: http::Request<B>) -> http_1::Request<B> {
+    let (parts, body) = req.into_parts();
+
+    let mut req = http_1::Request::new(body);
+    *req.method_mut() = method_to_http1(parts.method);
+    *req.uri_mut() = uri_to_http1(parts.uri);
+    *req.version_mut() = version_to_http1(parts.version);
+    *req.headers_mut() = headers_to_http1(parts.headers);
+    *req.extensions_mut() = extensions_to_http1(parts.extensions);
+    req
+}
+
+/// Convert an http 1.0 `Request` to an http 0.2 `Request`.
+pub fn request_from_http1<B>(req: http_1::Request<B>) -> http::Request<B> {
+    let (parts, body) = req.into_parts();
+
+    let mut req = http::Request::new(body);
+    *req.method_mut() = method_from_http1(parts.method);
+    *req.uri_mut() = uri_from_http1(parts.uri);
+    *req.version_mut() = version_from_http1(parts.version);
+    *req.headers_mut() = headers_from_http1(parts.headers);
+    *req.extensions_mut() = extensions_from_http1(parts.extensions);
+    req
+}
+
+/// Convert an http 0.2 `Response` to an http 1.0 `Response`.
+pub fn response_to_http1<B>(res: http::Response<B>) -> http_1::Response<B> {
+    let (parts, body) = res.into_parts();
+
+    let mut res = http_1::Response::new(body);
+    *res.status_mut() = status_to_http1(parts.status);
+    *res.version_mut() = version_to_http1(parts.version);
+    *res.headers_mut() = headers_to_http1(parts.headers);
+    *res.extensions_mut() = extensions_to_http1(parts.extensions);
+    res
+}
+
+/// Convert an http 1.0 `Response` to an http 0.2 `Response`.
+pub fn response_from_http1<B>(res: http_1::Response<B>) -> http::Response<B> {
+    let (parts, body) = res.into_parts();
+
+    let mut res = http::Response::new(body);
+    *res.status_mut() = status_from_http1(parts.status);
+    *res.version_mut() = version_from_http1(parts.version);
+    *res.headers_mut() = headers_from_http1(parts.headers);
+    *res.extensions_mut() = extensions_from_http1(parts.extensions);
+    res
+}
+
+/// Convert an http 0.2 `HeaderMap` to an http 1.0 `HeaderMap`.
+///
+/// This can also be used for trailers.
+pub fn headers_to_http1(headers: http::HeaderMap) -> http_1::HeaderMap {
+    let mut out = http_1::HeaderMap::with_capacity(headers.keys_len());
+    let mut name = None;
+    for (next_name, value) in headers {
+        if let Some(next_name) = next_name {
+            name = Some(header_name_to_http1(&next_name));
+        }
+        let name = name
+            .clone()
+            .expect("the first item yielded by `HeaderMap::into_iter` always has a name");
+        out.append(name, header_value_to_http1(&value));
+    }
+    out
+}
+
+/// Convert an http 1.0 `HeaderMap` to an http 0.2 `HeaderMap`.
+///
+/// This can also be used for trailers.
+pub fn headers_from_http1(headers: http_1::HeaderMap) -> http::HeaderMap {
+    let mut out = http::HeaderMap::with_capacity(headers.keys_len());
+    let mut name = None;
+    for (next_name, value) in headers {
+        if let Some(next_name) = next_name {
+            name = Some(header_name_from_http1(&next_name));
+        }
+        let name = name
+            .clone()
+            .expect("the first item yielded by `HeaderMap::into_iter` always has a name");
+        out.append(name, header_value_from_http1(&value));
+    }
+    out
+}
+
+fn header_name_to_http1(name: &http::HeaderName) -> http_1::HeaderName {
+    http_1::HeaderName::from_bytes(name.as_str().as_bytes())
+        .expect("header names are validated the same way by both versions")
+}
+
+fn header_name_from_http1(name: &http_1::HeaderName) -> http::HeaderName {
+    http::HeaderName::from_bytes(name.as_str().as_bytes())
+        .expect("header names are validated the same way by both versions")
+}
+
+fn header_value_to_http1(value: &http::HeaderValue) -> http_1::HeaderValue {
+    let mut out = http_1::HeaderValue::from_bytes(value.as_bytes())
+        .expect("header values are validated the same way by both versions");
+    out.set_sensitive(value.is_sensitive());
+    out
+}
+
+fn header_value_from_http1(value: &http_1::HeaderValue) -> http::HeaderValue {
+    let mut out = http::HeaderValue::from_bytes(value.as_bytes())
+        .expect("header values are validated the same way by both versions");
+    out.set_sensitive(value.is_sensitive());
+    out
+}
+
+/// Convert an http 0.2 `Method` to an http 1.0 `Method`.
+pub fn method_to_http1(method: http::Method) -> http_1::Method {
+    http_1::Method::from_bytes(method.as_str().as_bytes())
+        .expect("methods are validated the same way by both versions")
+}
+
+/// Convert an http 1.0 `Method` to an http 0.2 `Method`.
+pub fn method_from_http1(method: http_1::Method) -> http::Method {
+    http::Method::from_bytes(method.as_str().as_bytes())
+        .expect("methods are validated the same way by both versions")
+}
+
+/// Convert an http 0.2 `StatusCode` to an http 1.0 `StatusCode`.
+pub fn status_to_http1(status: http::StatusCode) -> http_1::StatusCode {
+    http_1::StatusCode::from_u16(status.as_u16())
+        .expect("status codes are validated the same way by both versions")
+}
+
+/// Convert an http 1.0 `StatusCode` to an http 0.2 `StatusCode`.
+pub fn status_from_http1(status: http_1::StatusCode) -> http::StatusCode {
+    http::StatusCode::from_u16(status.as_u16())
+        .expect("status codes are validated the same way by both versions")
+}
+
+/// Convert an http 0.2 `Uri` to an http 1.0 `Uri`.
+pub fn uri_to_http1(uri: http::Uri) -> http_1::Uri {
+    let parts = uri.into_parts();
+    let mut new_parts = http_1::uri::Parts::default();
+    new_parts.scheme = parts.scheme.map(|scheme| {
+        http_1::uri::Scheme::try_from(scheme.as_str())
+            .expect("schemes are validated the same way by both versions")
+    });
+    new_parts.authority = parts.authority.map(|authority| {
+        http_1::uri::Authority::try_from(authority.as_str())
+            .expect("authorities are validated the same way by both versions")
+    });
+    new_parts.path_and_query = parts.path_and_query.map(|path_and_query| {
+        http_1::uri::PathAndQuery::try_from(path_and_query.as_str())
+            .expect("paths are validated the same way by both versions")
+    });
+    http_1::Uri::from_parts(new_parts).expect("URIs are validated the same way by both versions")
+}
+
+/// Convert an http 1.0 `Uri` to an http 0.2 `Uri`.
+pub fn uri_from_http1(uri: http_1::Uri) -> http::Uri {
+    let parts = uri.into_parts();
+    let mut new_parts = http::uri::Parts::default();
+    new_parts.scheme = parts.scheme.map(|scheme| {
+        http::uri::Scheme::try_from(scheme.as_str())
+            .expect("schemes are validated the same way by both versions")
+    });
+    new_parts.authority = parts.authority.map(|authority| {
+        http::uri::Authority::try_from(authority.as_str())
+            .expect("authorities are validated the same way by both versions")
+    });
+    new_parts.path_and_query = parts.path_and_query.map(|path_and_query| {
+        http::uri::PathAndQuery::try_from(path_and_query.as_str())
+            .expect("paths are validated the same way by both versions")
+    });
+    http::Uri::from_parts(new_parts).expect("URIs are validated the same way by both versions")
+}
+
+/// Convert an http 0.2 `Version` to an http 1.0 `Version`.
+pub fn version_to_http1(version: http::Version) -> http_1::Version {
+    if version == http::Version::HTTP_09 {
+        http_1::Version::HTTP_09
+    } else if version == http::Version::HTTP_10 {
+        http_1::Version::HTTP_10
+    } else if version == http::Version::HTTP_2 {
+        http_1::Version::HTTP_2
+    } else if version == http::Version::HTTP_3 {
+        http_1::Version::HTTP_3
+    } else {
+        http_1::Version::HTTP_11
+    }
+}
+
+/// Convert an http 1.0 `Version` to an http 0.2 `Version`.
+pub fn version_from_http1(version: http_1::Version) -> http::Version {
+    if version == http_1::Version::HTTP_09 {
+        http::Version::HTTP_09
+    } else if version == http_1::Version::HTTP_10 {
+        http::Version::HTTP_10
+    } else if version == http_1::Version::HTTP_2 {
+        http::Version::HTTP_2
+    } else if version == http_1::Version::HTTP_3 {
+        http::Version::HTTP_3
+    } else {
+        http::Version::HTTP_11
+    }
+}
+
+/// Convert http 0.2 `Extensions` to http 1.0 `Extensions`.
+///
+/// If `extensions` contains [`Http1Extensions`], from a previous call to
+/// [`extensions_from_http1`], those are unwrapped and returned. Any other values are stored in
+/// the returned extensions as [`Http02Extensions`].
+pub fn extensions_to_http1(mut extensions: http::Extensions) -> http_1::Extensions {
+    let mut out = extensions
+        .remove::<Http1Extensions>()
+        .map(|Http1Extensions(extensions)| extensions)
+        .unwrap_or_default();
+    if !extensions.is_empty() {
+        out.insert(Http02Extensions(Arc::new(extensions)));
+    }
+    out
+}
+
+/// Convert http 1.0 `Extensions` to http 0.2 `Extensions`.
+///
+/// If `extensions` contains [`Http02Extensions`], from a previous call to
+/// [`extensions_to_http1`], those are unwrapped and returned. Any other values are stored in the
+/// returned extensions as [`Http1Extensions`].
+///
+/// [`Http02Extensions`] that have been cloned, and thus cannot be unwrapped, are left in place.
+pub fn extensions_from_http1(mut extensions: http_1::Extensions) -> http::Extensions {
+    let mut out = match extensions.remove::<Http02Extensions>() {
+        Some(Http02Extensions(inner)) => match Arc::try_unwrap(inner) {
+            Ok(inner) => inner,
+            Err(inner) => {
+                extensions.insert(Http02Extensions(inner));
+                http::Extensions::new()
+            }
+        },
+        None => http::Extensions::new(),
+    };
+    if !extensions.is_empty() {
+        out.insert(Http1Extensions(extensions));
+    }
+    out
+}
+
+/// http 1.0 `Extensions` stored in http 0.2 `Extensions`.
+///
+/// See the [module docs](self) for more details.
+#[derive(Clone, Default)]
+pub struct Http1Extensions(http_1::Extensions);
+
+impl Http1Extensions {
+    /// Get a reference to a value of type `T` in the wrapped extensions.
+    pub fn get<T>(&self) -> Option<&T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.0.get()
+    }
+
+    /// Get a mutable reference to a value of type `T` in the wrapped extensions.
+    pub fn get_mut<T>(&mut self) -> Option<&mut T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.0.get_mut()
+    }
+
+    /// Remove a value of type `T` from the wrapped extensions.
+    pub fn remove<T>(&mut self) -> Option<T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.0.remove()
+    }
+
+    /// Get a reference to the wrapped extensions.
+    pub fn get_ref(&self) -> &http_1::Extensions {
+        &self.0
+    }
+
+    /// Consume `self`, returning the wrapped extensions.
+    pub fn into_inner(self) -> http_1::Extensions {
+        self.0
+    }
+}
+
+impl fmt::Debug for Http1Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Http1Extensions").finish()
+    }
+}
+
+/// http 0.2 `Extensions` stored in http 1.0 `Extensions`.
+///
+/// http 1.0 requires extensions to implement `Clone`, which http 0.2's `Extensions` doesn't, so
+/// they are stored in an `Arc`.
+///
+/// See the [module docs](self) for more details.
+#[derive(Clone)]
+pub struct Http02Extensions(Arc<http::Extensions>);
+
+impl Http02Extensions {
+    /// Get a reference to a value of type `T` in the wrapped extensions.
+    pub fn get<T>(&self) -> Option<&T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.0.get()
+    }
+
+    /// Get a mutable reference to a value of type `T` in the wrapped extensions.
+    ///
+    /// Returns `None` if `self` has been cloned and other clones are still alive.
+    pub fn get_mut<T>(&mut self) -> Option<&mut T>
+    where
+        T: Send + Sync + 'static,
+    {
+        Arc::get_mut(&mut self.0)?.get_mut()
+    }
+
+    /// Remove a value of type `T` from the wrapped extensions.
+    ///
+    /// Returns `None` if `self` has been cloned and other clones are still alive.
+    pub fn remove<T>(&mut self) -> Option<T>
+    where
+        T: Send + Sync + 'static,
+    {
+        Arc::get_mut(&mut self.0)?.remove()
+    }
+
+    /// Get a reference to the wrapped extensions.
+    pub fn get_ref(&self) -> &http::Extensions {
+        &self.0
+    }
+
+    /// Consume `self`, returning the wrapped extensions.
+    ///
+    /// Returns `Err(self)` if `self` has been cloned and other clones are still alive.
+    pub fn try_into_inner(self) -> Result<http::Extensions, Self> {
+        Arc::try_unwrap(self.0).map_err(Self)
+    }
+}
+
+impl fmt::Debug for Http02Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Http02Extensions").finish()
+    }
+}
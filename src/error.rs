@@ -0,0 +1,70 @@
+use std::fmt;
+
+use tower::BoxError;
+
+/// Errors that can happen when using the body adapters in strict mode.
+///
+/// See [`HttpBody04ToHttpBody1::strict`] and [`HttpBody1ToHttpBody04::strict`], and
+/// [`Strict::limit`] for limiting the size of bodies.
+///
+/// [`HttpBody04ToHttpBody1::strict`]: crate::HttpBody04ToHttpBody1::strict
+/// [`HttpBody1ToHttpBody04::strict`]: crate::HttpBody1ToHttpBody04::strict
+/// [`Strict::limit`]: crate::Strict::limit
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The inner body produced an error.
+    Body(BoxError),
+    /// A data frame was received after a trailers frame.
+    DataAfterTrailers,
+    /// More than one trailers frame was received.
+    MultipleTrailers,
+    /// A frame that is neither data nor trailers was received.
+    UnknownFrame,
+    /// The body contained more data than allowed by the limit.
+    LengthLimitExceeded,
+    /// The body contained a different amount of data than its exact size hint promised.
+    SizeHintMismatch {
+        /// The number of bytes promised by the size hint.
+        expected: u64,
+        /// The number of bytes read when the mismatch was detected.
+        actual: u64,
+    },
+}
+
+impl Error {
+    /// Returns `true` if the error was produced by the inner body, rather than detected by the
+    /// adapter.
+    pub fn is_body(&self) -> bool {
+        matches!(self, Self::Body(_))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Body(_) => write!(f, "error from inner body"),
+            Self::DataAfterTrailers => write!(f, "received data frame after trailers"),
+            Self::MultipleTrailers => write!(f, "received more than one trailers frame"),
+            Self::UnknownFrame => write!(f, "received frame that is neither data nor trailers"),
+            Self::LengthLimitExceeded => write!(f, "length limit exceeded"),
+            Self::SizeHintMismatch { expected, actual } => write!(
+                f,
+                "body length doesn't match its size hint (expected {expected} bytes, got {actual})"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Body(err) => Some(&**err),
+            Self::DataAfterTrailers
+            | Self::MultipleTrailers
+            | Self::UnknownFrame
+            | Self::LengthLimitExceeded
+            | Self::SizeHintMismatch { .. } => None,
+        }
+    }
+}
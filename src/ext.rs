@@ -0,0 +1,210 @@
+use http_body_04::Body as _;
+use hyper_1::body::Bytes;
+use tower::BoxError;
+
+use crate::{
+    BoxBody04To1, BoxBody1To04, HttpBody04ToHttpBody1, HttpBody1ToHttpBody04, UnsyncBoxBody04To1,
+    UnsyncBoxBody1To04,
+};
+
+cfg_service! {
+    use http::Request;
+
+    use crate::{
+        hyper_http, Hyper1HttpServiceAsTowerService03HttpService,
+        Hyper1ServiceAsTowerService03Service, TowerService03HttpServiceAsHyper1HttpService,
+        TowerService03ServiceAsHyper1Service,
+    };
+
+    /// Extension trait for [tower-service 0.3 `Service`]s that adds methods for converting them to
+    /// [hyper 1.0 `Service`]s.
+    ///
+    /// [tower-service 0.3 `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
+    /// [hyper 1.0 `Service`]: https://docs.rs/hyper/1/hyper/service/trait.Service.html
+    pub trait TowerService03ServiceExt<R>: tower_service_03::Service<R> {
+        /// Convert this service to a [hyper 1.0 `Service`].
+        ///
+        /// See [`TowerService03ServiceAsHyper1Service`] for more details.
+        ///
+        /// [hyper 1.0 `Service`]: https://docs.rs/hyper/1/hyper/service/trait.Service.html
+        fn into_hyper1_service(self) -> TowerService03ServiceAsHyper1Service<Self>
+        where
+            Self: Sized,
+        {
+            TowerService03ServiceAsHyper1Service::new(self)
+        }
+    }
+
+    impl<S, R> TowerService03ServiceExt<R> for S where S: tower_service_03::Service<R> {}
+
+    /// Extension trait for [tower-service 0.3 HTTP `Service`]s that adds methods for converting
+    /// them to [hyper 1.0 HTTP `Service`]s.
+    ///
+    /// [tower-service 0.3 HTTP `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
+    /// [hyper 1.0 HTTP `Service`]: https://docs.rs/hyper/1/hyper/service/trait.Service.html
+    pub trait TowerService03HttpServiceExt<B>:
+        tower_service_03::Service<Request<HttpBody1ToHttpBody04<B>>>
+    {
+        /// Convert this service to a [hyper 1.0 HTTP `Service`].
+        ///
+        /// See [`TowerService03HttpServiceAsHyper1HttpService`] for more details.
+        ///
+        /// [hyper 1.0 HTTP `Service`]: https://docs.rs/hyper/1/hyper/service/trait.Service.html
+        fn into_hyper1_http_service(
+            self,
+        ) -> TowerService03HttpServiceAsHyper1HttpService<Self, HttpBody1ToHttpBody04<B>>
+        where
+            Self: Sized,
+        {
+            TowerService03HttpServiceAsHyper1HttpService::new(self)
+        }
+    }
+
+    impl<S, B> TowerService03HttpServiceExt<B> for S where
+        S: tower_service_03::Service<Request<HttpBody1ToHttpBody04<B>>>
+    {
+    }
+
+    /// Extension trait for [hyper 1.0 `Service`]s that adds methods for converting them to
+    /// [tower-service 0.3 `Service`]s.
+    ///
+    /// [tower-service 0.3 `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
+    /// [hyper 1.0 `Service`]: https://docs.rs/hyper/1/hyper/service/trait.Service.html
+    pub trait Hyper1ServiceExt<R>: hyper_1::service::Service<R> {
+        /// Convert this service to a [tower-service 0.3 `Service`].
+        ///
+        /// See [`Hyper1ServiceAsTowerService03Service`] for more details.
+        ///
+        /// [tower-service 0.3 `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
+        fn into_tower03_service(self) -> Hyper1ServiceAsTowerService03Service<Self>
+        where
+            Self: Sized,
+        {
+            Hyper1ServiceAsTowerService03Service::new(self)
+        }
+    }
+
+    impl<S, R> Hyper1ServiceExt<R> for S where S: hyper_1::service::Service<R> {}
+
+    /// Extension trait for [hyper 1.0 HTTP `Service`]s that adds methods for converting them to
+    /// [tower-service 0.3 HTTP `Service`]s.
+    ///
+    /// [tower-service 0.3 HTTP `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
+    /// [hyper 1.0 HTTP `Service`]: https://docs.rs/hyper/1/hyper/service/trait.Service.html
+    pub trait Hyper1HttpServiceExt<B>:
+        hyper_1::service::Service<hyper_http::Request<HttpBody04ToHttpBody1<B>>>
+    {
+        /// Convert this service to a [tower-service 0.3 HTTP `Service`].
+        ///
+        /// See [`Hyper1HttpServiceAsTowerService03HttpService`] for more details.
+        ///
+        /// [tower-service 0.3 HTTP `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
+        fn into_tower03_http_service(self) -> Hyper1HttpServiceAsTowerService03HttpService<Self, B>
+        where
+            Self: Sized,
+        {
+            Hyper1HttpServiceAsTowerService03HttpService::new(self)
+        }
+    }
+
+    impl<S, B> Hyper1HttpServiceExt<B> for S where
+        S: hyper_1::service::Service<hyper_http::Request<HttpBody04ToHttpBody1<B>>>
+    {
+    }
+}
+
+/// Extension trait for [http-body 0.4 `Body`]s that adds methods for converting them to
+/// [http-body 1.0 `Body`]s.
+///
+/// [http-body 0.4 `Body`]: https://docs.rs/http-body/0.4/http_body/trait.Body.html
+/// [http-body 1.0 `Body`]: https://docs.rs/http-body/1/http_body/trait.Body.html
+pub trait HttpBody04Ext: http_body_04::Body {
+    /// Convert this body to an [http-body 1.0 `Body`].
+    ///
+    /// See [`HttpBody04ToHttpBody1`] for more details.
+    ///
+    /// [http-body 1.0 `Body`]: https://docs.rs/http-body/1/http_body/trait.Body.html
+    fn into_http_body1(self) -> HttpBody04ToHttpBody1<Self>
+    where
+        Self: Sized,
+    {
+        HttpBody04ToHttpBody1::new(self)
+    }
+
+    /// Convert this body to a type-erased [http-body 1.0 `Body`].
+    ///
+    /// Errors are converted into [`BoxError`]. See [`BoxBody04To1`] for more details.
+    ///
+    /// [http-body 1.0 `Body`]: https://docs.rs/http-body/1/http_body/trait.Body.html
+    fn into_http_body1_boxed(self) -> BoxBody04To1
+    where
+        Self: http_body_04::Body<Data = Bytes> + Sized + Send + Sync + 'static,
+        Self::Error: Into<BoxError>,
+    {
+        HttpBody04ToHttpBody1::new(self.map_err(Into::into).boxed())
+    }
+
+    /// Convert this body to a type-erased [http-body 1.0 `Body`] that isn't `Sync`.
+    ///
+    /// Errors are converted into [`BoxError`]. See [`UnsyncBoxBody04To1`] for more details.
+    ///
+    /// [http-body 1.0 `Body`]: https://docs.rs/http-body/1/http_body/trait.Body.html
+    fn into_http_body1_boxed_unsync(self) -> UnsyncBoxBody04To1
+    where
+        Self: http_body_04::Body<Data = Bytes> + Sized + Send + 'static,
+        Self::Error: Into<BoxError>,
+    {
+        HttpBody04ToHttpBody1::new(self.map_err(Into::into).boxed_unsync())
+    }
+}
+
+impl<B> HttpBody04Ext for B where B: http_body_04::Body {}
+
+/// Extension trait for [http-body 1.0 `Body`]s that adds methods for converting them to
+/// [http-body 0.4 `Body`]s.
+///
+/// [http-body 0.4 `Body`]: https://docs.rs/http-body/0.4/http_body/trait.Body.html
+/// [http-body 1.0 `Body`]: https://docs.rs/http-body/1/http_body/trait.Body.html
+pub trait HttpBody1Ext: http_body_1::Body {
+    /// Convert this body to an [http-body 0.4 `Body`].
+    ///
+    /// See [`HttpBody1ToHttpBody04`] for more details.
+    ///
+    /// [http-body 0.4 `Body`]: https://docs.rs/http-body/0.4/http_body/trait.Body.html
+    fn into_http_body04(self) -> HttpBody1ToHttpBody04<Self>
+    where
+        Self: Sized,
+    {
+        HttpBody1ToHttpBody04::new(self)
+    }
+
+    /// Convert this body to a type-erased [http-body 0.4 `Body`].
+    ///
+    /// Errors are converted into [`BoxError`]. See [`BoxBody1To04`] for more details.
+    ///
+    /// [http-body 0.4 `Body`]: https://docs.rs/http-body/0.4/http_body/trait.Body.html
+    fn into_http_body04_boxed(self) -> BoxBody1To04
+    where
+        Self: http_body_1::Body<Data = Bytes> + Sized + Send + Sync + 'static,
+        Self::Error: Into<BoxError>,
+    {
+        HttpBody1ToHttpBody04::new(self).map_err(Into::into).boxed()
+    }
+
+    /// Convert this body to a type-erased [http-body 0.4 `Body`] that isn't `Sync`.
+    ///
+    /// Errors are converted into [`BoxError`]. See [`UnsyncBoxBody1To04`] for more details.
+    ///
+    /// [http-body 0.4 `Body`]: https://docs.rs/http-body/0.4/http_body/trait.Body.html
+    fn into_http_body04_boxed_unsync(self) -> UnsyncBoxBody1To04
+    where
+        Self: http_body_1::Body<Data = Bytes> + Sized + Send + 'static,
+        Self::Error: Into<BoxError>,
+    {
+        HttpBody1ToHttpBody04::new(self)
+            .map_err(Into::into)
+            .boxed_unsync()
+    }
+}
+
+impl<B> HttpBody1Ext for B where B: http_body_1::Body {}
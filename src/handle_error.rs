@@ -0,0 +1,144 @@
+use std::{
+    convert::Infallible,
+    fmt::Debug,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project_lite::pin_project;
+use tower::{util::Oneshot, ServiceExt};
+
+/// A [tower-service 0.3 `Service`] that converts errors from the inner service into responses.
+///
+/// hyper 1.0 closes the connection when a service returns an error, so fallible services, such as
+/// services with timeouts, should be wrapped in `HandleError` before being served.
+///
+/// `f` is called with the error and returns the response to send instead, for example a
+/// `500 Internal Server Error`.
+///
+/// Like [`TowerService03HttpServiceAsHyper1HttpService`], `HandleError` is always ready and
+/// drives the readiness of a clone of the inner service for each request, so errors from
+/// `poll_ready` are converted as well.
+///
+/// [tower-service 0.3 `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
+/// [`TowerService03HttpServiceAsHyper1HttpService`]: crate::TowerService03HttpServiceAsHyper1HttpService
+#[derive(Clone, Copy)]
+pub struct HandleError<S, F> {
+    service: S,
+    f: F,
+}
+
+impl<S, F> HandleError<S, F> {
+    /// Create a new `HandleError`.
+    #[inline]
+    pub fn new(service: S, f: F) -> Self {
+        Self { service, f }
+    }
+}
+
+impl<S, F> Debug for HandleError<S, F>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HandleError")
+            .field("service", &self.service)
+            .finish()
+    }
+}
+
+impl<S, F, R> tower_service_03::Service<R> for HandleError<S, F>
+where
+    S: tower_service_03::Service<R> + Clone,
+    F: FnOnce(S::Error) -> S::Response + Clone,
+{
+    type Response = S::Response;
+    type Error = Infallible;
+    type Future = HandleErrorFuture<S, R, F>;
+
+    #[inline]
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn call(&mut self, req: R) -> Self::Future {
+        HandleErrorFuture {
+            future: self.service.clone().oneshot(req),
+            f: Some(self.f.clone()),
+        }
+    }
+}
+
+pin_project! {
+    /// Response future for [`HandleError`].
+    pub struct HandleErrorFuture<S, R, F>
+    where
+        S: tower_service_03::Service<R>,
+    {
+        #[pin]
+        future: Oneshot<S, R>,
+        f: Option<F>,
+    }
+}
+
+impl<S, R, F> Debug for HandleErrorFuture<S, R, F>
+where
+    S: tower_service_03::Service<R>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HandleErrorFuture").finish_non_exhaustive()
+    }
+}
+
+impl<S, R, F> Future for HandleErrorFuture<S, R, F>
+where
+    S: tower_service_03::Service<R>,
+    F: FnOnce(S::Error) -> S::Response,
+{
+    type Output = Result<S::Response, Infallible>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let res = ready!(this.future.poll(cx)).unwrap_or_else(|err| {
+            let f = this.f.take().expect("future polled after completion");
+            f(err)
+        });
+        Poll::Ready(Ok(res))
+    }
+}
+
+/// A [tower-layer 0.3 `Layer`] that produces [`HandleError`]s.
+///
+/// [tower-layer 0.3 `Layer`]: https://docs.rs/tower-layer/latest/tower_layer/trait.Layer.html
+#[derive(Clone, Copy)]
+pub struct HandleErrorLayer<F> {
+    f: F,
+}
+
+impl<F> HandleErrorLayer<F> {
+    /// Create a new `HandleErrorLayer`.
+    #[inline]
+    pub fn new(f: F) -> Self {
+        Self { f }
+    }
+}
+
+impl<F> Debug for HandleErrorLayer<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HandleErrorLayer").finish_non_exhaustive()
+    }
+}
+
+impl<S, F> tower_layer_03::Layer<S> for HandleErrorLayer<F>
+where
+    F: Clone,
+{
+    type Service = HandleError<S, F>;
+
+    #[inline]
+    fn layer(&self, inner: S) -> Self::Service {
+        HandleError::new(inner, self.f.clone())
+    }
+}
@@ -10,7 +10,15 @@ use http::{Request, Response};
 use pin_project_lite::pin_project;
 use tower::{util::Oneshot, ServiceExt};
 
-use crate::{HttpBody04ToHttpBody1, HttpBody1ToHttpBody04};
+#[cfg(feature = "buffer")]
+use crate::Buffered;
+use crate::{
+    hyper_http::{
+        self, request_from_hyper, request_to_hyper, response_from_hyper, response_to_hyper,
+    },
+    AddConnectionInfo, AlwaysReady, BoxResponseBody, HandleError, HttpBody04ToHttpBody1,
+    HttpBody1ToHttpBody04, MapErrToBoxError, PollReady, RequestBodyLimit,
+};
 
 // --- tower-service 0.3 (http) to hyper 1.0 (http) ---
 
@@ -19,6 +27,10 @@ use crate::{HttpBody04ToHttpBody1, HttpBody1ToHttpBody04};
 /// An HTTP `Service` is a `Service` where the request is [`http::Request<_>`][Request] and the
 /// response is [`http::Response<_>`][Response].
 ///
+/// The tower-service 0.3 service uses http 0.2 and http-body 0.4, while hyper 1.0 uses http 1.0
+/// and http-body 1.0. Requests and responses are converted as described in the
+/// [`convert`](crate::convert) module.
+///
 /// # Example
 ///
 /// ```no_run
@@ -26,7 +38,7 @@ use crate::{HttpBody04ToHttpBody1, HttpBody1ToHttpBody04};
 /// use hyper_1::{server::conn::http1, service::service_fn, body, body::Bytes};
 /// use std::{net::SocketAddr, convert::Infallible};
 /// use tokio::net::TcpListener;
-/// use tower_hyper_http_body_compat::TowerService03HttpServiceAsHyper1HttpService;
+/// use tower_hyper_http_body_compat::{TokioIoToHyper1Io, TowerService03HttpServiceAsHyper1HttpService};
 ///
 /// // a service function that uses hyper 0.14, tower-service 0.3, and http-body 0.4
 /// async fn handler<B>(req: Request<B>) -> Result<Response<hyper_014::body::Body>, Infallible>
@@ -34,9 +46,7 @@ use crate::{HttpBody04ToHttpBody1, HttpBody1ToHttpBody04};
 ///     B: hyper_014::body::HttpBody<Data = hyper_014::body::Bytes>,
 ///     B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
 /// {
-///    let body = req.into_body();
-///    let body = http_body_04::Limited::new(body, 1024);
-///    let bytes = match hyper_014::body::to_bytes(body).await {
+///    let bytes = match hyper_014::body::to_bytes(req.into_body()).await {
 ///        Ok(bytes) => bytes,
 ///        Err(err) => {
 ///            let res = Response::builder()
@@ -59,12 +69,14 @@ use crate::{HttpBody04ToHttpBody1, HttpBody1ToHttpBody04};
 ///     let addr: SocketAddr = ([127, 0, 0, 1], 8080).into();
 ///
 ///     let service = tower::service_fn(handler);
-///     let service = TowerService03HttpServiceAsHyper1HttpService::new(service);
+///     let service = TowerService03HttpServiceAsHyper1HttpService::new(service)
+///         // reject request bodies larger than 1024 bytes
+///         .request_body_limit(1024);
 ///
 ///     let mut tcp_listener = TcpListener::bind(addr).await?;
 ///     loop {
 ///         let (tcp_stream, _) = tcp_listener.accept().await?;
-///         let tcp_stream = hyper_util::rt::TokioIo::new(tcp_stream);
+///         let tcp_stream = TokioIoToHyper1Io::new(tcp_stream);
 ///         let service = service.clone();
 ///         tokio::task::spawn(async move {
 ///             if let Err(http_err) = http1::Builder::new()
@@ -78,8 +90,16 @@ use crate::{HttpBody04ToHttpBody1, HttpBody1ToHttpBody04};
 /// }
 /// ```
 ///
+/// # Backpressure
+///
+/// hyper 1.0's `Service` trait doesn't have a `poll_ready` method so each request clones the
+/// inner service and drives its readiness on the clone. Use
+/// [`TowerService03HttpServiceAsHyper1HttpService::buffered`] if the service contains
+/// middleware that keeps readiness state per instance, such as `tower::limit::RateLimit` or a
+/// custom `poll_ready` that reserves a slot.
+///
 /// [tower-service 0.3 HTTP `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
-/// [hyper 1.0 HTTP `Service`]: https://docs.rs/hyper/1.0.0-rc.4/hyper/service/trait.Service.html
+/// [hyper 1.0 HTTP `Service`]: https://docs.rs/hyper/1/hyper/service/trait.Service.html
 pub struct TowerService03HttpServiceAsHyper1HttpService<S, B> {
     service: S,
     _marker: PhantomData<fn() -> B>,
@@ -96,6 +116,108 @@ impl<S, B> TowerService03HttpServiceAsHyper1HttpService<S, B> {
     }
 }
 
+impl<S, B> TowerService03HttpServiceAsHyper1HttpService<S, B> {
+    /// Box the response bodies of the inner service.
+    ///
+    /// The hyper 1.0 `Service` then responds with [`UnsyncBoxBody04To1`] regardless of the inner
+    /// service's response body type, which makes the service easier to name, for example when
+    /// storing it in a struct. Errors from the response body are converted into
+    /// [`tower::BoxError`].
+    ///
+    /// See [`BoxResponseBody`] for more details.
+    ///
+    /// [`UnsyncBoxBody04To1`]: crate::UnsyncBoxBody04To1
+    pub fn box_response_body(
+        self,
+    ) -> TowerService03HttpServiceAsHyper1HttpService<BoxResponseBody<S>, B> {
+        TowerService03HttpServiceAsHyper1HttpService::new(BoxResponseBody::new(self.service))
+    }
+
+    /// Convert errors from the inner service into responses using `f`.
+    ///
+    /// hyper 1.0 closes the connection when a service returns an error, so this should be used
+    /// for services that can fail. See [`HandleError`] for more details.
+    pub fn handle_error<F>(
+        self,
+        f: F,
+    ) -> TowerService03HttpServiceAsHyper1HttpService<HandleError<S, F>, B> {
+        TowerService03HttpServiceAsHyper1HttpService::new(HandleError::new(self.service, f))
+    }
+
+    /// Limit request bodies to `limit` bytes.
+    ///
+    /// Requests whose body is known to exceed the limit, or exceeds it before the inner service
+    /// responds, are rejected with `413 Payload Too Large`. See [`RequestBodyLimit`] for more
+    /// details.
+    pub fn request_body_limit(
+        self,
+        limit: usize,
+    ) -> TowerService03HttpServiceAsHyper1HttpService<RequestBodyLimit<S>, B> {
+        TowerService03HttpServiceAsHyper1HttpService::new(RequestBodyLimit::new(
+            self.service,
+            limit,
+        ))
+    }
+
+    /// Insert `info` into the extensions of every request.
+    ///
+    /// Use this to attach information about the connection being served, such as its
+    /// [`ConnectionInfo`]. See [`AddConnectionInfo`] for more details.
+    ///
+    /// [`ConnectionInfo`]: crate::ConnectionInfo
+    pub fn connection_info<T>(
+        self,
+        info: T,
+    ) -> TowerService03HttpServiceAsHyper1HttpService<AddConnectionInfo<S, T>, B> {
+        TowerService03HttpServiceAsHyper1HttpService::new(AddConnectionInfo::new(
+            self.service,
+            info,
+        ))
+    }
+
+    /// Convert errors from the inner service into [`tower::BoxError`]s.
+    ///
+    /// This is useful when the hyper 1.0 service must have a specific error type, as hyper 1.0's
+    /// connections require errors that can be converted into [`tower::BoxError`].
+    ///
+    /// See [`MapErrToBoxError`] for more details.
+    pub fn box_error(self) -> TowerService03HttpServiceAsHyper1HttpService<MapErrToBoxError<S>, B> {
+        TowerService03HttpServiceAsHyper1HttpService::new(MapErrToBoxError::new(self.service))
+    }
+}
+
+#[cfg(feature = "buffer")]
+impl<S, ReqBody>
+    TowerService03HttpServiceAsHyper1HttpService<
+        Buffered<S, Request<HttpBody1ToHttpBody04<ReqBody>>>,
+        HttpBody1ToHttpBody04<ReqBody>,
+    >
+where
+    S: tower_service_03::Service<Request<HttpBody1ToHttpBody04<ReqBody>>>,
+    S::Error: Into<tower::BoxError>,
+{
+    /// Create a new `TowerService03HttpServiceAsHyper1HttpService` that drives backpressure on a
+    /// single instance of `service`.
+    ///
+    /// `service` is moved into a [`Buffered`] whose worker task calls `poll_ready` on it before
+    /// dispatching each request, so middleware that keeps readiness state per instance (such as
+    /// `RateLimit`) behaves as it would under hyper 0.14. `bound` is the maximum number of
+    /// requests that can be queued while `service` isn't ready.
+    ///
+    /// Errors are boxed.
+    ///
+    /// This must be called from within a tokio runtime since it spawns the buffer's worker task.
+    pub fn buffered(service: S, bound: usize) -> Self
+    where
+        S: Send + 'static,
+        S::Future: Send,
+        S::Error: Send + Sync,
+        ReqBody: Send + 'static,
+    {
+        Self::new(Buffered::new(service, bound))
+    }
+}
+
 impl<S, B> Copy for TowerService03HttpServiceAsHyper1HttpService<S, B> where S: Copy {}
 
 impl<S, B> Clone for TowerService03HttpServiceAsHyper1HttpService<S, B>
@@ -121,7 +243,7 @@ where
     }
 }
 
-impl<S, ReqBody, ResBody> hyper_1::service::Service<Request<ReqBody>>
+impl<S, ReqBody, ResBody> hyper_1::service::Service<hyper_http::Request<ReqBody>>
     for TowerService03HttpServiceAsHyper1HttpService<S, HttpBody1ToHttpBody04<ReqBody>>
 where
     S: tower_service_03::Service<
@@ -129,7 +251,7 @@ where
             Response = Response<ResBody>,
         > + Clone,
 {
-    type Response = Response<HttpBody04ToHttpBody1<ResBody>>;
+    type Response = hyper_http::Response<HttpBody04ToHttpBody1<ResBody>>;
     type Error = S::Error;
     type Future = TowerService03HttpServiceAsHyper1HttpServiceFuture<
         S,
@@ -137,8 +259,8 @@ where
     >;
 
     #[inline]
-    fn call(&self, req: Request<ReqBody>) -> Self::Future {
-        let req = req.map(HttpBody1ToHttpBody04::new);
+    fn call(&self, req: hyper_http::Request<ReqBody>) -> Self::Future {
+        let req = request_from_hyper(req).map(HttpBody1ToHttpBody04::new);
         TowerService03HttpServiceAsHyper1HttpServiceFuture {
             future: self.service.clone().oneshot(req),
         }
@@ -160,12 +282,63 @@ impl<S, R, B> Future for TowerService03HttpServiceAsHyper1HttpServiceFuture<S, R
 where
     S: tower_service_03::Service<R, Response = Response<B>>,
 {
-    type Output = Result<Response<HttpBody04ToHttpBody1<B>>, S::Error>;
+    type Output = Result<hyper_http::Response<HttpBody04ToHttpBody1<B>>, S::Error>;
 
     #[inline]
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let res = ready!(self.project().future.poll(cx))?;
-        Poll::Ready(Ok(res.map(HttpBody04ToHttpBody1::new)))
+        Poll::Ready(Ok(response_to_hyper(res).map(HttpBody04ToHttpBody1::new)))
+    }
+}
+
+/// A [tower-layer 0.3 `Layer`] that produces [`TowerService03HttpServiceAsHyper1HttpService`]s.
+///
+/// This allows the conversion to happen in the middle of a [`tower::ServiceBuilder`] such that
+/// hyper 1.0 middleware can be applied on the outside and tower-service 0.3 middleware on the
+/// inside.
+///
+/// [tower-layer 0.3 `Layer`]: https://docs.rs/tower-layer/latest/tower_layer/trait.Layer.html
+pub struct TowerService03HttpServiceAsHyper1HttpServiceLayer<B> {
+    _marker: PhantomData<fn() -> B>,
+}
+
+impl<B> TowerService03HttpServiceAsHyper1HttpServiceLayer<B> {
+    /// Create a new `TowerService03HttpServiceAsHyper1HttpServiceLayer`.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<B> Default for TowerService03HttpServiceAsHyper1HttpServiceLayer<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B> Copy for TowerService03HttpServiceAsHyper1HttpServiceLayer<B> {}
+
+impl<B> Clone for TowerService03HttpServiceAsHyper1HttpServiceLayer<B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<B> Debug for TowerService03HttpServiceAsHyper1HttpServiceLayer<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TowerService03HttpServiceAsHyper1HttpServiceLayer")
+            .finish()
+    }
+}
+
+impl<S, B> tower_layer_03::Layer<S> for TowerService03HttpServiceAsHyper1HttpServiceLayer<B> {
+    type Service = TowerService03HttpServiceAsHyper1HttpService<S, B>;
+
+    #[inline]
+    fn layer(&self, inner: S) -> Self::Service {
+        TowerService03HttpServiceAsHyper1HttpService::new(inner)
     }
 }
 
@@ -176,10 +349,22 @@ where
 /// An HTTP `Service` is a `Service` where the request is [`http::Request<_>`][Request] and the
 /// response is [`http::Response<_>`][Response].
 ///
+/// The hyper 1.0 service uses http 1.0 and http-body 1.0, while tower-service 0.3 services use
+/// http 0.2 and http-body 0.4. Requests and responses are converted as described in the
+/// [`convert`](crate::convert) module.
+///
+/// # Backpressure
+///
+/// hyper 1.0's `Service` trait doesn't have a `poll_ready` method so by default this service is
+/// always ready. Use [`Hyper1HttpServiceAsTowerService03HttpService::with_poll_ready`] to attach
+/// a readiness source such that tower middleware like `Buffer` or `LoadShed` can observe
+/// pressure from the hyper 1.0 service.
+///
 /// [tower-service 0.3 HTTP `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
-/// [hyper 1.0 HTTP `Service`]: https://docs.rs/hyper/1.0.0-rc.4/hyper/service/trait.Service.html
-pub struct Hyper1HttpServiceAsTowerService03HttpService<S, B> {
+/// [hyper 1.0 HTTP `Service`]: https://docs.rs/hyper/1/hyper/service/trait.Service.html
+pub struct Hyper1HttpServiceAsTowerService03HttpService<S, B, P = AlwaysReady> {
     service: S,
+    poll_ready: P,
     _marker: PhantomData<fn() -> B>,
 }
 
@@ -188,12 +373,30 @@ impl<S, B> Hyper1HttpServiceAsTowerService03HttpService<S, B> {
     pub fn new(service: S) -> Self {
         Self {
             service,
+            poll_ready: AlwaysReady::new(),
             _marker: PhantomData,
         }
     }
 }
 
-impl<S, B> Debug for Hyper1HttpServiceAsTowerService03HttpService<S, B>
+impl<S, B, P> Hyper1HttpServiceAsTowerService03HttpService<S, B, P> {
+    /// Use `poll_ready` to determine when the service is ready to accept a request.
+    ///
+    /// `poll_ready` can be any [`PollReady`], such as a closure with the signature
+    /// `FnMut(&mut Context<'_>) -> Poll<Result<(), S::Error>>`.
+    pub fn with_poll_ready<T>(
+        self,
+        poll_ready: T,
+    ) -> Hyper1HttpServiceAsTowerService03HttpService<S, B, T> {
+        Hyper1HttpServiceAsTowerService03HttpService {
+            service: self.service,
+            poll_ready,
+            _marker: self._marker,
+        }
+    }
+}
+
+impl<S, B, P> Debug for Hyper1HttpServiceAsTowerService03HttpService<S, B, P>
 where
     S: Debug,
 {
@@ -204,39 +407,47 @@ where
     }
 }
 
-impl<S, B> Clone for Hyper1HttpServiceAsTowerService03HttpService<S, B>
+impl<S, B, P> Clone for Hyper1HttpServiceAsTowerService03HttpService<S, B, P>
 where
     S: Clone,
+    P: Clone,
 {
     fn clone(&self) -> Self {
         Self {
             service: self.service.clone(),
+            poll_ready: self.poll_ready.clone(),
             _marker: self._marker,
         }
     }
 }
 
-impl<S, B> Copy for Hyper1HttpServiceAsTowerService03HttpService<S, B> where S: Copy {}
+impl<S, B, P> Copy for Hyper1HttpServiceAsTowerService03HttpService<S, B, P>
+where
+    S: Copy,
+    P: Copy,
+{
+}
 
-impl<S, ReqBody, ResBody> tower_service_03::Service<Request<ReqBody>>
-    for Hyper1HttpServiceAsTowerService03HttpService<S, ReqBody>
+impl<S, P, ReqBody, ResBody> tower_service_03::Service<Request<ReqBody>>
+    for Hyper1HttpServiceAsTowerService03HttpService<S, ReqBody, P>
 where
     S: hyper_1::service::Service<
-        Request<HttpBody04ToHttpBody1<ReqBody>>,
-        Response = Response<ResBody>,
+        hyper_http::Request<HttpBody04ToHttpBody1<ReqBody>>,
+        Response = hyper_http::Response<ResBody>,
     >,
+    P: PollReady<S::Error>,
 {
     type Response = Response<HttpBody1ToHttpBody04<ResBody>>;
     type Error = S::Error;
     type Future = Hyper1HttpServiceAsTowerService03HttpServiceFuture<S::Future>;
 
     #[inline]
-    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        Poll::Ready(Ok(()))
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.poll_ready.poll_ready(cx)
     }
 
     fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
-        let req = req.map(HttpBody04ToHttpBody1::new);
+        let req = request_to_hyper(req.map(HttpBody04ToHttpBody1::new));
         Hyper1HttpServiceAsTowerService03HttpServiceFuture {
             future: self.service.call(req),
         }
@@ -253,13 +464,64 @@ pin_project! {
 
 impl<F, B, E> Future for Hyper1HttpServiceAsTowerService03HttpServiceFuture<F>
 where
-    F: Future<Output = Result<Response<B>, E>>,
+    F: Future<Output = Result<hyper_http::Response<B>, E>>,
 {
     type Output = Result<Response<HttpBody1ToHttpBody04<B>>, E>;
 
     #[inline]
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let res = ready!(self.project().future.poll(cx))?;
-        Poll::Ready(Ok(res.map(HttpBody1ToHttpBody04::new)))
+        Poll::Ready(Ok(response_from_hyper(res).map(HttpBody1ToHttpBody04::new)))
+    }
+}
+
+/// A [tower-layer 0.3 `Layer`] that produces [`Hyper1HttpServiceAsTowerService03HttpService`]s.
+///
+/// This allows the conversion to happen in the middle of a [`tower::ServiceBuilder`] such that
+/// tower-service 0.3 middleware can be applied on the outside and hyper 1.0 middleware on the
+/// inside.
+///
+/// [tower-layer 0.3 `Layer`]: https://docs.rs/tower-layer/latest/tower_layer/trait.Layer.html
+pub struct Hyper1HttpServiceAsTowerService03HttpServiceLayer<B> {
+    _marker: PhantomData<fn() -> B>,
+}
+
+impl<B> Hyper1HttpServiceAsTowerService03HttpServiceLayer<B> {
+    /// Create a new `Hyper1HttpServiceAsTowerService03HttpServiceLayer`.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<B> Default for Hyper1HttpServiceAsTowerService03HttpServiceLayer<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B> Copy for Hyper1HttpServiceAsTowerService03HttpServiceLayer<B> {}
+
+impl<B> Clone for Hyper1HttpServiceAsTowerService03HttpServiceLayer<B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<B> Debug for Hyper1HttpServiceAsTowerService03HttpServiceLayer<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Hyper1HttpServiceAsTowerService03HttpServiceLayer")
+            .finish()
+    }
+}
+
+impl<S, B> tower_layer_03::Layer<S> for Hyper1HttpServiceAsTowerService03HttpServiceLayer<B> {
+    type Service = Hyper1HttpServiceAsTowerService03HttpService<S, B>;
+
+    #[inline]
+    fn layer(&self, inner: S) -> Self::Service {
+        Hyper1HttpServiceAsTowerService03HttpService::new(inner)
     }
 }
@@ -0,0 +1,189 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project_lite::pin_project;
+
+use crate::convert::{
+    request_from_http1, request_to_http1, response_from_http1, response_to_http1,
+};
+
+// --- http 0.2 to http 1.0 ---
+
+/// Converts a [tower-service 0.3 `Service`] that uses http 0.2's `Request` and `Response` to
+/// one that uses http 1.0's `Request` and `Response`.
+///
+/// Requests are converted with [`request_from_http1`] and responses with
+/// [`response_to_http1`]. See the [`convert`](crate::convert) module for details.
+///
+/// [tower-service 0.3 `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
+#[derive(Clone, Copy, Debug)]
+pub struct Http02ServiceAsHttp1Service<S>(S);
+
+impl<S> Http02ServiceAsHttp1Service<S> {
+    /// Create a new `Http02ServiceAsHttp1Service`.
+    #[inline]
+    pub fn new(inner: S) -> Self {
+        Self(inner)
+    }
+}
+
+impl<S, ReqBody, ResBody> tower_service_03::Service<http_1::Request<ReqBody>>
+    for Http02ServiceAsHttp1Service<S>
+where
+    S: tower_service_03::Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+{
+    type Response = http_1::Response<ResBody>;
+    type Error = S::Error;
+    type Future = Http02ServiceAsHttp1ServiceFuture<S::Future>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_ready(cx)
+    }
+
+    #[inline]
+    fn call(&mut self, req: http_1::Request<ReqBody>) -> Self::Future {
+        Http02ServiceAsHttp1ServiceFuture {
+            future: self.0.call(request_from_http1(req)),
+        }
+    }
+}
+
+pin_project! {
+    /// Response future for [`Http02ServiceAsHttp1Service`].
+    #[derive(Debug)]
+    pub struct Http02ServiceAsHttp1ServiceFuture<F> {
+        #[pin]
+        future: F,
+    }
+}
+
+impl<F, B, E> Future for Http02ServiceAsHttp1ServiceFuture<F>
+where
+    F: Future<Output = Result<http::Response<B>, E>>,
+{
+    type Output = Result<http_1::Response<B>, E>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let res = ready!(self.project().future.poll(cx))?;
+        Poll::Ready(Ok(response_to_http1(res)))
+    }
+}
+
+/// A [tower-layer 0.3 `Layer`] that produces [`Http02ServiceAsHttp1Service`]s.
+///
+/// [tower-layer 0.3 `Layer`]: https://docs.rs/tower-layer/latest/tower_layer/trait.Layer.html
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Http02ServiceAsHttp1ServiceLayer {
+    _priv: (),
+}
+
+impl Http02ServiceAsHttp1ServiceLayer {
+    /// Create a new `Http02ServiceAsHttp1ServiceLayer`.
+    #[inline]
+    pub fn new() -> Self {
+        Self { _priv: () }
+    }
+}
+
+impl<S> tower_layer_03::Layer<S> for Http02ServiceAsHttp1ServiceLayer {
+    type Service = Http02ServiceAsHttp1Service<S>;
+
+    #[inline]
+    fn layer(&self, inner: S) -> Self::Service {
+        Http02ServiceAsHttp1Service::new(inner)
+    }
+}
+
+// --- http 1.0 to http 0.2 ---
+
+/// Converts a [tower-service 0.3 `Service`] that uses http 1.0's `Request` and `Response` to
+/// one that uses http 0.2's `Request` and `Response`.
+///
+/// Requests are converted with [`request_to_http1`] and responses with
+/// [`response_from_http1`]. See the [`convert`](crate::convert) module for details.
+///
+/// [tower-service 0.3 `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
+#[derive(Clone, Copy, Debug)]
+pub struct Http1ServiceAsHttp02Service<S>(S);
+
+impl<S> Http1ServiceAsHttp02Service<S> {
+    /// Create a new `Http1ServiceAsHttp02Service`.
+    #[inline]
+    pub fn new(inner: S) -> Self {
+        Self(inner)
+    }
+}
+
+impl<S, ReqBody, ResBody> tower_service_03::Service<http::Request<ReqBody>>
+    for Http1ServiceAsHttp02Service<S>
+where
+    S: tower_service_03::Service<http_1::Request<ReqBody>, Response = http_1::Response<ResBody>>,
+{
+    type Response = http::Response<ResBody>;
+    type Error = S::Error;
+    type Future = Http1ServiceAsHttp02ServiceFuture<S::Future>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_ready(cx)
+    }
+
+    #[inline]
+    fn call(&mut self, req: http::Request<ReqBody>) -> Self::Future {
+        Http1ServiceAsHttp02ServiceFuture {
+            future: self.0.call(request_to_http1(req)),
+        }
+    }
+}
+
+pin_project! {
+    /// Response future for [`Http1ServiceAsHttp02Service`].
+    #[derive(Debug)]
+    pub struct Http1ServiceAsHttp02ServiceFuture<F> {
+        #[pin]
+        future: F,
+    }
+}
+
+impl<F, B, E> Future for Http1ServiceAsHttp02ServiceFuture<F>
+where
+    F: Future<Output = Result<http_1::Response<B>, E>>,
+{
+    type Output = Result<http::Response<B>, E>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let res = ready!(self.project().future.poll(cx))?;
+        Poll::Ready(Ok(response_from_http1(res)))
+    }
+}
+
+/// A [tower-layer 0.3 `Layer`] that produces [`Http1ServiceAsHttp02Service`]s.
+///
+/// [tower-layer 0.3 `Layer`]: https://docs.rs/tower-layer/latest/tower_layer/trait.Layer.html
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Http1ServiceAsHttp02ServiceLayer {
+    _priv: (),
+}
+
+impl Http1ServiceAsHttp02ServiceLayer {
+    /// Create a new `Http1ServiceAsHttp02ServiceLayer`.
+    #[inline]
+    pub fn new() -> Self {
+        Self { _priv: () }
+    }
+}
+
+impl<S> tower_layer_03::Layer<S> for Http1ServiceAsHttp02ServiceLayer {
+    type Service = Http1ServiceAsHttp02Service<S>;
+
+    #[inline]
+    fn layer(&self, inner: S) -> Self::Service {
+        Http1ServiceAsHttp02Service::new(inner)
+    }
+}
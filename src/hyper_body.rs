@@ -0,0 +1,370 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use http_body_1::Frame;
+use hyper_1::body::{Bytes, Incoming};
+
+use crate::{
+    hyper_http::{self, headers_from_hyper, headers_to_hyper},
+    HttpBody1ToHttpBody04,
+};
+
+// --- hyper 1.0 `Incoming` ---
+
+/// Converts hyper 1.0's [`Incoming`] to an [http-body 0.4 `Body`].
+///
+/// This does the same as [`HttpBody1ToHttpBody04<Incoming>`] but, since `Incoming` only ever
+/// yields data followed by at most one set of trailers, it doesn't need a [`FramePolicy`] or to
+/// keep polling after the trailers have been received.
+///
+/// It also implements [http-body 1.0 `Body`] by polling the inner `Incoming` directly, so it can
+/// be handed back to hyper 1.0 without another adapter. Use
+/// [`Hyper1IncomingAsHyper014Body::into_inner`] to get the `Incoming` back.
+///
+/// Code that requires hyper 0.14's `Body` itself, such as tonic's `Routes`, can get one with
+/// [`AsHyper014Body::into_hyper014_body`].
+///
+/// [http-body 0.4 `Body`]: https://docs.rs/http-body/0.4/http_body/trait.Body.html
+/// [http-body 1.0 `Body`]: https://docs.rs/http-body/1/http_body/trait.Body.html
+/// [`HttpBody1ToHttpBody04<Incoming>`]: crate::HttpBody1ToHttpBody04
+/// [`FramePolicy`]: crate::FramePolicy
+#[derive(Debug)]
+pub struct Hyper1IncomingAsHyper014Body {
+    body: Incoming,
+    trailers: Option<hyper_http::HeaderMap>,
+    done: bool,
+}
+
+impl Hyper1IncomingAsHyper014Body {
+    /// Create a new `Hyper1IncomingAsHyper014Body`.
+    #[inline]
+    pub fn new(body: Incoming) -> Self {
+        Self {
+            body,
+            trailers: None,
+            done: false,
+        }
+    }
+
+    /// Get the inner `Incoming` back.
+    ///
+    /// Trailers received through [`poll_data`] but not yet taken with [`poll_trailers`] are
+    /// dropped.
+    ///
+    /// [`poll_data`]: http_body_04::Body::poll_data
+    /// [`poll_trailers`]: http_body_04::Body::poll_trailers
+    #[inline]
+    pub fn into_inner(self) -> Incoming {
+        self.body
+    }
+}
+
+impl From<Incoming> for Hyper1IncomingAsHyper014Body {
+    #[inline]
+    fn from(body: Incoming) -> Self {
+        Self::new(body)
+    }
+}
+
+impl http_body_04::Body for Hyper1IncomingAsHyper014Body {
+    type Data = Bytes;
+    type Error = hyper_1::Error;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        if self.done || self.trailers.is_some() {
+            return Poll::Ready(None);
+        }
+
+        match ready!(http_body_1::Body::poll_frame(Pin::new(&mut self.body), cx)) {
+            Some(Ok(frame)) => match frame.into_data() {
+                Ok(data) => Poll::Ready(Some(Ok(data))),
+                Err(frame) => {
+                    // `Incoming` only yields data and trailers
+                    self.trailers = frame.into_trailers().ok();
+                    self.done = self.trailers.is_none();
+                    Poll::Ready(None)
+                }
+            },
+            Some(Err(err)) => Poll::Ready(Some(Err(err))),
+            None => {
+                self.done = true;
+                Poll::Ready(None)
+            }
+        }
+    }
+
+    fn poll_trailers(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        loop {
+            if let Some(trailers) = self.trailers.take() {
+                self.done = true;
+                return Poll::Ready(Ok(Some(headers_from_hyper(trailers))));
+            }
+
+            if self.done {
+                return Poll::Ready(Ok(None));
+            }
+
+            match ready!(http_body_1::Body::poll_frame(Pin::new(&mut self.body), cx)) {
+                Some(Ok(frame)) => {
+                    // data that wasn't consumed through `poll_data` is discarded
+                    if let Ok(trailers) = frame.into_trailers() {
+                        self.trailers = Some(trailers);
+                    }
+                }
+                Some(Err(err)) => return Poll::Ready(Err(err)),
+                None => self.done = true,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> http_body_04::SizeHint {
+        let size_hint = http_body_1::Body::size_hint(&self.body);
+        let mut out = http_body_04::SizeHint::new();
+        out.set_lower(size_hint.lower());
+        if let Some(upper) = size_hint.upper() {
+            out.set_upper(upper);
+        }
+        out
+    }
+
+    #[inline]
+    fn is_end_stream(&self) -> bool {
+        self.trailers.is_none() && (self.done || http_body_1::Body::is_end_stream(&self.body))
+    }
+}
+
+impl http_body_1::Body for Hyper1IncomingAsHyper014Body {
+    type Data = Bytes;
+    type Error = hyper_1::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        if let Some(trailers) = self.trailers.take() {
+            self.done = true;
+            return Poll::Ready(Some(Ok(Frame::trailers(trailers))));
+        }
+
+        if self.done {
+            return Poll::Ready(None);
+        }
+
+        http_body_1::Body::poll_frame(Pin::new(&mut self.body), cx)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> http_body_1::SizeHint {
+        http_body_1::Body::size_hint(&self.body)
+    }
+
+    #[inline]
+    fn is_end_stream(&self) -> bool {
+        http_body_04::Body::is_end_stream(self)
+    }
+}
+
+// --- conversion into hyper 0.14 `Body` ---
+
+/// Converts a body into hyper 0.14's [`Body`] without going through another adapter.
+///
+/// hyper 0.14's `Body` can only be created from a stream of data, which
+/// [`Body::wrap_stream`] boxes. Converting an http-body 0.4 `Body` that way polls it through
+/// two layers of adapters. The implementations of this trait instead poll hyper 1.0's
+/// [`Incoming`] directly or return hyper 0.14's `Body` as is.
+///
+/// When an [`Incoming`] is converted, trailers are dropped, as hyper 0.14's `Body` has no way to
+/// send them from a stream. Bodies that have already ended are converted to [`Body::empty`], so
+/// their size hint and [`is_end_stream`] are kept. Otherwise the size hint is unknown.
+///
+/// [`Body`]: https://docs.rs/hyper/0.14/hyper/body/struct.Body.html
+/// [`Body::wrap_stream`]: https://docs.rs/hyper/0.14/hyper/body/struct.Body.html#method.wrap_stream
+/// [`Body::empty`]: https://docs.rs/hyper/0.14/hyper/body/struct.Body.html#method.empty
+/// [`is_end_stream`]: http_body_04::Body::is_end_stream
+pub trait AsHyper014Body {
+    /// Convert `self` into hyper 0.14's `Body`.
+    fn into_hyper014_body(self) -> hyper_014::Body;
+}
+
+impl AsHyper014Body for hyper_014::Body {
+    #[inline]
+    fn into_hyper014_body(self) -> hyper_014::Body {
+        self
+    }
+}
+
+impl AsHyper014Body for Hyper014BodyAsHyper1Body {
+    #[inline]
+    fn into_hyper014_body(self) -> hyper_014::Body {
+        self.body
+    }
+}
+
+impl AsHyper014Body for Hyper1IncomingAsHyper014Body {
+    fn into_hyper014_body(self) -> hyper_014::Body {
+        // pending trailers are dropped along with any that the stream would receive
+        if self.done || self.trailers.is_some() || http_body_1::Body::is_end_stream(&self.body) {
+            return hyper_014::Body::empty();
+        }
+        hyper_014::Body::wrap_stream(IncomingData { body: self.body })
+    }
+}
+
+impl AsHyper014Body for HttpBody1ToHttpBody04<Incoming> {
+    #[inline]
+    fn into_hyper014_body(self) -> hyper_014::Body {
+        let (body, data_ended) = self.into_data_parts();
+        Hyper1IncomingAsHyper014Body {
+            body,
+            trailers: None,
+            done: data_ended,
+        }
+        .into_hyper014_body()
+    }
+}
+
+/// The data frames of an [`Incoming`] as a `Stream`.
+struct IncomingData {
+    body: Incoming,
+}
+
+impl futures_core::Stream for IncomingData {
+    type Item = Result<Bytes, hyper_1::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match ready!(http_body_1::Body::poll_frame(Pin::new(&mut self.body), cx)) {
+                Some(Ok(frame)) => {
+                    // `Incoming` only yields data and trailers, which are dropped
+                    if let Ok(data) = frame.into_data() {
+                        return Poll::Ready(Some(Ok(data)));
+                    }
+                }
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+// --- hyper 0.14 `Body` ---
+
+/// Converts hyper 0.14's [`Body`] to an [http-body 1.0 `Body`].
+///
+/// This does the same as [`HttpBody04ToHttpBody1<hyper::Body>`] but without a [`FramePolicy`].
+///
+/// It also implements [http-body 0.4 `Body`] by polling the inner `Body` directly, so it can be
+/// handed back to hyper 0.14 or axum 0.6 without another adapter. Use
+/// [`Hyper014BodyAsHyper1Body::into_inner`] to get the `Body` back.
+///
+/// [`Body`]: https://docs.rs/hyper/0.14/hyper/body/struct.Body.html
+/// [http-body 0.4 `Body`]: https://docs.rs/http-body/0.4/http_body/trait.Body.html
+/// [http-body 1.0 `Body`]: https://docs.rs/http-body/1/http_body/trait.Body.html
+/// [`HttpBody04ToHttpBody1<hyper::Body>`]: crate::HttpBody04ToHttpBody1
+/// [`FramePolicy`]: crate::FramePolicy
+#[derive(Debug, Default)]
+pub struct Hyper014BodyAsHyper1Body {
+    body: hyper_014::Body,
+}
+
+impl Hyper014BodyAsHyper1Body {
+    /// Create a new `Hyper014BodyAsHyper1Body`.
+    #[inline]
+    pub fn new(body: hyper_014::Body) -> Self {
+        Self { body }
+    }
+
+    /// Get the inner `Body` back.
+    #[inline]
+    pub fn into_inner(self) -> hyper_014::Body {
+        self.body
+    }
+}
+
+impl From<hyper_014::Body> for Hyper014BodyAsHyper1Body {
+    #[inline]
+    fn from(body: hyper_014::Body) -> Self {
+        Self::new(body)
+    }
+}
+
+impl http_body_1::Body for Hyper014BodyAsHyper1Body {
+    type Data = Bytes;
+    type Error = hyper_014::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let body = Pin::new(&mut self.body);
+        match http_body_04::Body::poll_data(body, cx) {
+            Poll::Ready(Some(Ok(data))) => return Poll::Ready(Some(Ok(Frame::data(data)))),
+            Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+            Poll::Ready(None) => {}
+            Poll::Pending => return Poll::Pending,
+        }
+
+        let body = Pin::new(&mut self.body);
+        match ready!(http_body_04::Body::poll_trailers(body, cx)) {
+            Ok(Some(trailers)) => {
+                Poll::Ready(Some(Ok(Frame::trailers(headers_to_hyper(trailers)))))
+            }
+            Ok(None) => Poll::Ready(None),
+            Err(err) => Poll::Ready(Some(Err(err))),
+        }
+    }
+
+    fn size_hint(&self) -> http_body_1::SizeHint {
+        let size_hint = http_body_04::Body::size_hint(&self.body);
+        let mut out = http_body_1::SizeHint::new();
+        out.set_lower(size_hint.lower());
+        if let Some(upper) = size_hint.upper() {
+            out.set_upper(upper);
+        }
+        out
+    }
+
+    #[inline]
+    fn is_end_stream(&self) -> bool {
+        http_body_04::Body::is_end_stream(&self.body)
+    }
+}
+
+impl http_body_04::Body for Hyper014BodyAsHyper1Body {
+    type Data = Bytes;
+    type Error = hyper_014::Error;
+
+    #[inline]
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        Pin::new(&mut self.body).poll_data(cx)
+    }
+
+    #[inline]
+    fn poll_trailers(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        Pin::new(&mut self.body).poll_trailers(cx)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> http_body_04::SizeHint {
+        self.body.size_hint()
+    }
+
+    #[inline]
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+}
@@ -0,0 +1,115 @@
+//! The version of the `http` crate used by hyper 1.0 and http-body 1.0.
+//!
+//! The final releases use http 1.0 while the release candidates use http 0.2. The adapters name
+//! the types of the hyper 1.0 side through hyper's re-exports and convert them with
+//! [`Http02Compat`], which is implemented for both versions of `http`. With the release
+//! candidates the conversions return their argument unchanged.
+
+// not every adapter is enabled in every feature combination
+#![allow(dead_code, unused_imports)]
+
+pub(crate) use hyper_1::{HeaderMap, Request, Response, Uri};
+
+use crate::convert;
+
+/// Conversion between a type of the `http` crate used by hyper 1.0 and its http 0.2 version.
+pub(crate) trait Http02Compat: Sized {
+    type Http02;
+
+    fn from_http02(value: Self::Http02) -> Self;
+
+    fn into_http02(self) -> Self::Http02;
+}
+
+macro_rules! impl_http02_compat {
+    (<$($param:ident),*> $http_1:ty => $http_02:ty, $to_http1:path, $from_http1:path) => {
+        impl<$($param),*> Http02Compat for $http_02 {
+            type Http02 = Self;
+
+            #[inline]
+            fn from_http02(value: Self::Http02) -> Self {
+                value
+            }
+
+            #[inline]
+            fn into_http02(self) -> Self::Http02 {
+                self
+            }
+        }
+
+        impl<$($param),*> Http02Compat for $http_1 {
+            type Http02 = $http_02;
+
+            #[inline]
+            fn from_http02(value: Self::Http02) -> Self {
+                $to_http1(value)
+            }
+
+            #[inline]
+            fn into_http02(self) -> Self::Http02 {
+                $from_http1(self)
+            }
+        }
+    };
+}
+
+impl_http02_compat!(
+    <B> http_1::Request<B> => http::Request<B>,
+    convert::request_to_http1,
+    convert::request_from_http1
+);
+impl_http02_compat!(
+    <B> http_1::Response<B> => http::Response<B>,
+    convert::response_to_http1,
+    convert::response_from_http1
+);
+impl_http02_compat!(
+    <> http_1::HeaderMap => http::HeaderMap,
+    convert::headers_to_http1,
+    convert::headers_from_http1
+);
+impl_http02_compat!(
+    <> http_1::Uri => http::Uri,
+    convert::uri_to_http1,
+    convert::uri_from_http1
+);
+
+#[inline]
+pub(crate) fn request_to_hyper<B>(req: http::Request<B>) -> Request<B> {
+    Request::from_http02(req)
+}
+
+#[inline]
+pub(crate) fn request_from_hyper<B>(req: Request<B>) -> http::Request<B> {
+    req.into_http02()
+}
+
+#[inline]
+pub(crate) fn response_to_hyper<B>(res: http::Response<B>) -> Response<B> {
+    Response::from_http02(res)
+}
+
+#[inline]
+pub(crate) fn response_from_hyper<B>(res: Response<B>) -> http::Response<B> {
+    res.into_http02()
+}
+
+#[inline]
+pub(crate) fn headers_to_hyper(headers: http::HeaderMap) -> HeaderMap {
+    HeaderMap::from_http02(headers)
+}
+
+#[inline]
+pub(crate) fn headers_from_hyper(headers: HeaderMap) -> http::HeaderMap {
+    headers.into_http02()
+}
+
+#[inline]
+pub(crate) fn uri_to_hyper(uri: http::Uri) -> Uri {
+    Uri::from_http02(uri)
+}
+
+#[inline]
+pub(crate) fn uri_from_hyper(uri: Uri) -> http::Uri {
+    uri.into_http02()
+}
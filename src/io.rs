@@ -0,0 +1,407 @@
+use std::{
+    fmt, io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project_lite::pin_project;
+
+/// Read from a tokio `AsyncRead` into a hyper 1.0 `ReadBufCursor`.
+pub(crate) fn poll_read_tokio_as_hyper1<T>(
+    io: Pin<&mut T>,
+    cx: &mut Context<'_>,
+    read_buf: &mut ReadBuffer,
+    mut buf: hyper_1::rt::ReadBufCursor<'_>,
+) -> Poll<io::Result<()>>
+where
+    T: tokio::io::AsyncRead + ?Sized,
+{
+    // `ReadBufCursor` only exposes its uninitialized memory through `unsafe` methods so read into
+    // the adapter's buffer and copy from that
+    let scratch = read_buf.get();
+    let len = buf.remaining().min(scratch.len());
+    let mut tbuf = tokio::io::ReadBuf::new(&mut scratch[..len]);
+    match tokio::io::AsyncRead::poll_read(io, cx, &mut tbuf) {
+        Poll::Ready(Ok(())) => {}
+        other => return other,
+    }
+
+    buf.put_slice(tbuf.filled());
+    Poll::Ready(Ok(()))
+}
+
+/// Scratch buffer used by [`poll_read_tokio_as_hyper1`].
+///
+/// It is allocated and zeroed on the first read and reused by every read after that. Cloning
+/// doesn't copy the contents.
+#[derive(Default)]
+pub(crate) struct ReadBuffer {
+    buf: Option<Box<[u8]>>,
+}
+
+impl ReadBuffer {
+    const SIZE: usize = 8 * 1024;
+
+    fn get(&mut self) -> &mut [u8] {
+        self.buf
+            .get_or_insert_with(|| vec![0; Self::SIZE].into_boxed_slice())
+    }
+}
+
+impl Clone for ReadBuffer {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Debug for ReadBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadBuffer").finish()
+    }
+}
+
+/// Read from a hyper 1.0 `Read` into a tokio `ReadBuf`.
+pub(crate) fn poll_read_hyper1_as_tokio<T>(
+    io: Pin<&mut T>,
+    cx: &mut Context<'_>,
+    tbuf: &mut tokio::io::ReadBuf<'_>,
+) -> Poll<io::Result<()>>
+where
+    T: hyper_1::rt::Read + ?Sized,
+{
+    let n = {
+        let mut buf = hyper_1::rt::ReadBuf::new(tbuf.initialize_unfilled());
+        match hyper_1::rt::Read::poll_read(io, cx, buf.unfilled()) {
+            Poll::Ready(Ok(())) => buf.filled().len(),
+            other => return other,
+        }
+    };
+
+    tbuf.advance(n);
+    Poll::Ready(Ok(()))
+}
+
+pin_project! {
+    /// Converts an IO object implementing tokio's [`AsyncRead`] and [`AsyncWrite`] to one
+    /// implementing [hyper 1.0's `rt::Read` and `rt::Write`].
+    ///
+    /// This can be used to serve a `tokio::net::TcpStream` with hyper 1.0 or to use IO objects
+    /// from hyper 0.14 connectors with hyper 1.0 connections.
+    ///
+    /// The tokio traits are also implemented by passing through to the inner IO object.
+    ///
+    /// [`AsyncRead`]: tokio::io::AsyncRead
+    /// [`AsyncWrite`]: tokio::io::AsyncWrite
+    /// [hyper 1.0's `rt::Read` and `rt::Write`]: https://docs.rs/hyper/1/hyper/rt/index.html
+    #[derive(Debug, Clone, Default)]
+    pub struct TokioIoToHyper1Io<T> {
+        #[pin]
+        io: T,
+        read_buf: ReadBuffer,
+    }
+}
+
+impl<T> TokioIoToHyper1Io<T> {
+    /// Create a new `TokioIoToHyper1Io`.
+    #[inline]
+    pub fn new(io: T) -> Self {
+        Self {
+            io,
+            read_buf: ReadBuffer::default(),
+        }
+    }
+
+    /// Get a reference to the inner IO object.
+    #[inline]
+    pub fn get_ref(&self) -> &T {
+        &self.io
+    }
+
+    /// Get a mutable reference to the inner IO object.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.io
+    }
+
+    /// Consume `self`, returning the inner IO object.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.io
+    }
+}
+
+impl<T> hyper_1::rt::Read for TokioIoToHyper1Io<T>
+where
+    T: tokio::io::AsyncRead,
+{
+    #[inline]
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: hyper_1::rt::ReadBufCursor<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.project();
+        poll_read_tokio_as_hyper1(this.io, cx, this.read_buf, buf)
+    }
+}
+
+impl<T> hyper_1::rt::Write for TokioIoToHyper1Io<T>
+where
+    T: tokio::io::AsyncWrite,
+{
+    #[inline]
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        tokio::io::AsyncWrite::poll_write(self.project().io, cx, buf)
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        tokio::io::AsyncWrite::poll_flush(self.project().io, cx)
+    }
+
+    #[inline]
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        tokio::io::AsyncWrite::poll_shutdown(self.project().io, cx)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        tokio::io::AsyncWrite::is_write_vectored(&self.io)
+    }
+
+    #[inline]
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        tokio::io::AsyncWrite::poll_write_vectored(self.project().io, cx, bufs)
+    }
+}
+
+impl<T> tokio::io::AsyncRead for TokioIoToHyper1Io<T>
+where
+    T: tokio::io::AsyncRead,
+{
+    #[inline]
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        tokio::io::AsyncRead::poll_read(self.project().io, cx, buf)
+    }
+}
+
+impl<T> tokio::io::AsyncWrite for TokioIoToHyper1Io<T>
+where
+    T: tokio::io::AsyncWrite,
+{
+    #[inline]
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        tokio::io::AsyncWrite::poll_write(self.project().io, cx, buf)
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        tokio::io::AsyncWrite::poll_flush(self.project().io, cx)
+    }
+
+    #[inline]
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        tokio::io::AsyncWrite::poll_shutdown(self.project().io, cx)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        tokio::io::AsyncWrite::is_write_vectored(&self.io)
+    }
+
+    #[inline]
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        tokio::io::AsyncWrite::poll_write_vectored(self.project().io, cx, bufs)
+    }
+}
+
+pin_project! {
+    /// Converts an IO object implementing [hyper 1.0's `rt::Read` and `rt::Write`] to one
+    /// implementing tokio's [`AsyncRead`] and [`AsyncWrite`].
+    ///
+    /// This can be used to pass IO objects from hyper 1.0, such as upgraded connections, to code
+    /// written for tokio or hyper 0.14.
+    ///
+    /// hyper 1.0's traits are also implemented by passing through to the inner IO object.
+    ///
+    /// [`AsyncRead`]: tokio::io::AsyncRead
+    /// [`AsyncWrite`]: tokio::io::AsyncWrite
+    /// [hyper 1.0's `rt::Read` and `rt::Write`]: https://docs.rs/hyper/1/hyper/rt/index.html
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct Hyper1IoToTokioIo<T> {
+        #[pin]
+        io: T,
+    }
+}
+
+impl<T> Hyper1IoToTokioIo<T> {
+    /// Create a new `Hyper1IoToTokioIo`.
+    #[inline]
+    pub fn new(io: T) -> Self {
+        Self { io }
+    }
+
+    /// Get a reference to the inner IO object.
+    #[inline]
+    pub fn get_ref(&self) -> &T {
+        &self.io
+    }
+
+    /// Get a mutable reference to the inner IO object.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.io
+    }
+
+    /// Consume `self`, returning the inner IO object.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.io
+    }
+}
+
+impl<T> tokio::io::AsyncRead for Hyper1IoToTokioIo<T>
+where
+    T: hyper_1::rt::Read,
+{
+    #[inline]
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        poll_read_hyper1_as_tokio(self.project().io, cx, buf)
+    }
+}
+
+impl<T> tokio::io::AsyncWrite for Hyper1IoToTokioIo<T>
+where
+    T: hyper_1::rt::Write,
+{
+    #[inline]
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        hyper_1::rt::Write::poll_write(self.project().io, cx, buf)
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        hyper_1::rt::Write::poll_flush(self.project().io, cx)
+    }
+
+    #[inline]
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        hyper_1::rt::Write::poll_shutdown(self.project().io, cx)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        hyper_1::rt::Write::is_write_vectored(&self.io)
+    }
+
+    #[inline]
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        hyper_1::rt::Write::poll_write_vectored(self.project().io, cx, bufs)
+    }
+}
+
+impl<T> hyper_1::rt::Read for Hyper1IoToTokioIo<T>
+where
+    T: hyper_1::rt::Read,
+{
+    #[inline]
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: hyper_1::rt::ReadBufCursor<'_>,
+    ) -> Poll<io::Result<()>> {
+        hyper_1::rt::Read::poll_read(self.project().io, cx, buf)
+    }
+}
+
+impl<T> hyper_1::rt::Write for Hyper1IoToTokioIo<T>
+where
+    T: hyper_1::rt::Write,
+{
+    #[inline]
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        hyper_1::rt::Write::poll_write(self.project().io, cx, buf)
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        hyper_1::rt::Write::poll_flush(self.project().io, cx)
+    }
+
+    #[inline]
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        hyper_1::rt::Write::poll_shutdown(self.project().io, cx)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        hyper_1::rt::Write::is_write_vectored(&self.io)
+    }
+
+    #[inline]
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        hyper_1::rt::Write::poll_write_vectored(self.project().io, cx, bufs)
+    }
+}
+
+#[cfg(all(feature = "hyper-014", feature = "client"))]
+impl<T> hyper_014::client::connect::Connection for TokioIoToHyper1Io<T>
+where
+    T: hyper_014::client::connect::Connection,
+{
+    #[inline]
+    fn connected(&self) -> hyper_014::client::connect::Connected {
+        self.io.connected()
+    }
+}
+
+#[cfg(all(feature = "hyper-014", feature = "client"))]
+impl<T> hyper_014::client::connect::Connection for Hyper1IoToTokioIo<T>
+where
+    T: hyper_014::client::connect::Connection,
+{
+    #[inline]
+    fn connected(&self) -> hyper_014::client::connect::Connected {
+        self.io.connected()
+    }
+}
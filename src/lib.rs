@@ -1,10 +1,5 @@
 //! Adapters between hyper 0.14-1.0, http-body 0.4-1.0, and tower-service 0.3.
 //!
-//! The required release candidates are:
-//!
-//! - hyper 1.0.0-rc.4
-//! - http-body 1.0.0-rc.2
-//!
 //! # Example
 //!
 //! Running an axum `Router` with hyper 1.0:
@@ -16,7 +11,7 @@
 //! use std::net::SocketAddr;
 //! use tokio::net::TcpListener;
 //! use tower_http::trace::TraceLayer;
-//! use tower_hyper_http_body_compat::TowerService03HttpServiceAsHyper1HttpService;
+//! use tower_hyper_http_body_compat::{TokioIoToHyper1Io, TowerService03HttpServiceAsHyper1HttpService};
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -35,9 +30,9 @@
 //!     loop {
 //!         let (tcp_stream, _) = tcp_listener.accept().await?;
 //!
-//!         // hyper-util isn't on crates.io yet. Instead depend on it via git
-//!         // `hyper-util = { git = "https://github.com/hyperium/hyper-util" }`
-//!         let tcp_stream = hyper_util::rt::TokioIo::new(tcp_stream);
+//!         // `TcpStream` implements tokio's IO traits. Convert that to something that
+//!         // implements hyper 1.0's IO traits.
+//!         let tcp_stream = TokioIoToHyper1Io::new(tcp_stream);
 //!
 //!         let service = service.clone();
 //!         tokio::task::spawn(async move {
@@ -60,13 +55,48 @@
 //! To enable the `Service` adapters you must enable either `http1` or `http2` and `server` or
 //! `client` (i.e. `(http1 || http2) && (server || client)`).
 //!
-//! The `Body` adapters are always enabled.
+//! The `Body` adapters and the conversions between http 0.2 and http 1.0 in [`convert`] are
+//! always enabled.
+//!
+//! The following optional features are also available:
+//!
+//! - `buffer`: Enables `TowerService03ServiceAsHyper1Service::buffered` and
+//!   `TowerService03HttpServiceAsHyper1HttpService::buffered`, which drive backpressure on a
+//!   single instance of the inner service.
+//! - `tokio`: Enables [`TokioIoToHyper1Io`] and [`Hyper1IoToTokioIo`] for converting between
+//!   tokio's and hyper 1.0's IO traits, [`TokioExecutor`] and [`TokioTimer`] for hyper 1.0's
+//!   runtime traits, and the [`upgrade`] module for handling HTTP upgrades. Together with
+//!   `server` it also enables the `server` module for accepting connections and shutting down
+//!   gracefully, including a `Server` with the same API as hyper 0.14's.
+//! - `hyper-014`: Enables `Hyper1IncomingAsHyper014Body` and `Hyper014BodyAsHyper1Body` for
+//!   converting between hyper 1.0's `Incoming` and hyper 0.14's `Body` without a
+//!   `FramePolicy`, the `tonic` module for serving tonic's `Routes` with hyper 1.0, support
+//!   for upgrades performed by hyper 0.14 in the [`upgrade`] module and, together with
+//!   `tokio`, `Hyper014ExecutorAsHyper1Executor`. Together with `tokio` and `client` it also
+//!   enables `Hyper014ConnectorAsHyper1Connector` and `Hyper1ConnectorAsHyper014Connector` for
+//!   using hyper 0.14 connectors with hyper 1.0 and vice versa.
+//!
+//! # Release candidates
+//!
+//! Both the final releases of hyper 1.0 and http-body 1.0 and their release candidates hyper
+//! 1.0.0-rc.4 and http-body 1.0.0-rc.2 are supported. The release candidates use http 0.2, so
+//! with them the hyper 1.0 side of the adapters uses http 0.2's types and nothing is converted.
+//!
+//! Cargo considers the release candidates semver compatible with the final releases and
+//! resolves the latter by default. To use the release candidates, pin them with exact version
+//! requirements in your own `Cargo.toml`:
+//!
+//! ```toml
+//! [dependencies]
+//! hyper = "=1.0.0-rc.4"
+//! http-body = "=1.0.0-rc.2"
+//! ```
 
 #![warn(
     clippy::all,
     clippy::dbg_macro,
     clippy::todo,
-    clippy::empty_enum,
+    clippy::empty_enums,
     clippy::enum_glob_use,
     clippy::mem_forget,
     clippy::unused_self,
@@ -75,9 +105,7 @@
     clippy::needless_borrow,
     clippy::match_wildcard_for_single_variants,
     clippy::if_let_mutex,
-    clippy::mismatched_target_os,
     clippy::await_holding_lock,
-    clippy::match_on_vec_items,
     clippy::imprecise_flops,
     clippy::suboptimal_flops,
     clippy::lossy_float_literal,
@@ -97,7 +125,7 @@
     missing_debug_implementations,
     missing_docs
 )]
-#![deny(unreachable_pub, private_in_public)]
+#![deny(unreachable_pub)]
 #![allow(elided_lifetimes_in_paths, clippy::type_complexity)]
 #![forbid(unsafe_code)]
 #![cfg_attr(docsrs, feature(doc_auto_cfg, doc_cfg))]
@@ -127,18 +155,100 @@ macro_rules! ready {
 }
 
 cfg_service! {
+    #[cfg(feature = "buffer")]
+    mod buffer;
     mod service;
     mod http_service;
+    mod make_service;
 
-    pub use service::{Hyper1ServiceAsTowerService03Service, TowerService03ServiceAsHyper1Service};
+    pub use service::{
+        AlwaysReady, Hyper1ServiceAsTowerService03Service,
+        Hyper1ServiceAsTowerService03ServiceLayer, PollReady, TowerService03ServiceAsHyper1Service,
+        TowerService03ServiceAsHyper1ServiceLayer,
+    };
     pub use http_service::{
-        Hyper1HttpServiceAsTowerService03HttpService, TowerService03HttpServiceAsHyper1HttpService,
+        Hyper1HttpServiceAsTowerService03HttpService,
+        Hyper1HttpServiceAsTowerService03HttpServiceLayer,
+        TowerService03HttpServiceAsHyper1HttpService,
+        TowerService03HttpServiceAsHyper1HttpServiceLayer,
     };
+    pub use make_service::TowerService03MakeServiceAsHyper1MakeService;
+    #[cfg(feature = "buffer")]
+    pub use buffer::Buffered;
 }
 
+#[cfg(all(feature = "client", any(feature = "http1", feature = "http2")))]
+mod client;
+
 mod body;
+mod box_response_body;
+mod connection_info;
+mod error;
+mod ext;
+mod handle_error;
+mod http_version;
+mod hyper_http;
+mod limit;
+mod map_err_to_box_error;
+
+pub mod convert;
 
-pub use body::{HttpBody04ToHttpBody1, HttpBody1ToHttpBody04};
+#[cfg(feature = "hyper-014")]
+mod hyper_body;
+#[cfg(feature = "hyper-014")]
+pub mod tonic;
+
+#[cfg(all(feature = "tokio", feature = "hyper-014", feature = "client"))]
+mod connect;
+#[cfg(feature = "tokio")]
+mod io;
+#[cfg(feature = "tokio")]
+mod rt;
+#[cfg(all(
+    feature = "server",
+    feature = "tokio",
+    any(feature = "http1", feature = "http2")
+))]
+pub mod server;
+#[cfg(feature = "tokio")]
+pub mod upgrade;
+
+#[cfg(all(feature = "client", any(feature = "http1", feature = "http2")))]
+pub use client::Hyper1SendRequestAsTowerService03HttpService;
+#[cfg(all(feature = "tokio", feature = "hyper-014", feature = "client"))]
+pub use connect::{Hyper014ConnectorAsHyper1Connector, Hyper1ConnectorAsHyper014Connector};
+#[cfg(feature = "tokio")]
+pub use io::{Hyper1IoToTokioIo, TokioIoToHyper1Io};
+#[cfg(all(feature = "tokio", feature = "hyper-014"))]
+pub use rt::Hyper014ExecutorAsHyper1Executor;
+#[cfg(feature = "tokio")]
+pub use rt::{TokioExecutor, TokioTimer};
+
+pub use body::{
+    BoxBody04To1, BoxBody1To04, DropUnexpectedFrames, FramePolicy, FrameStreamBody,
+    HttpBody04ToHttpBody1, HttpBody1ToHttpBody04, StreamBody, Strict, UnexpectedFrame,
+    UnsyncBoxBody04To1, UnsyncBoxBody1To04,
+};
+pub use box_response_body::{BoxResponseBody, BoxResponseBodyLayer};
+pub use connection_info::{AddConnectionInfo, AddConnectionInfoLayer, ConnectionInfo};
+pub use error::Error;
+pub use ext::{HttpBody04Ext, HttpBody1Ext};
+pub use handle_error::{HandleError, HandleErrorLayer};
+pub use http_version::{
+    Http02ServiceAsHttp1Service, Http02ServiceAsHttp1ServiceLayer, Http1ServiceAsHttp02Service,
+    Http1ServiceAsHttp02ServiceLayer,
+};
+#[cfg(feature = "hyper-014")]
+pub use hyper_body::{AsHyper014Body, Hyper014BodyAsHyper1Body, Hyper1IncomingAsHyper014Body};
+pub use limit::{RequestBodyLimit, RequestBodyLimitLayer};
+pub use map_err_to_box_error::{MapErrToBoxError, MapErrToBoxErrorLayer};
+
+cfg_service! {
+    pub use ext::{
+        Hyper1HttpServiceExt, Hyper1ServiceExt, TowerService03HttpServiceExt,
+        TowerService03ServiceExt,
+    };
+}
 
 #[cfg(test)]
 mod tests;
@@ -146,11 +256,29 @@ mod tests;
 pub mod future {
     //! Future types.
 
+    pub use crate::box_response_body::BoxResponseBodyFuture;
+    pub use crate::handle_error::HandleErrorFuture;
+    pub use crate::http_version::{
+        Http02ServiceAsHttp1ServiceFuture, Http1ServiceAsHttp02ServiceFuture,
+    };
+    pub use crate::limit::RequestBodyLimitFuture;
+    pub use crate::map_err_to_box_error::MapErrToBoxErrorFuture;
+
+    #[cfg(all(feature = "client", any(feature = "http1", feature = "http2")))]
+    pub use crate::client::Hyper1SendRequestAsTowerService03HttpServiceFuture;
+    #[cfg(all(feature = "tokio", feature = "hyper-014", feature = "client"))]
+    pub use crate::connect::{
+        Hyper014ConnectorAsHyper1ConnectorFuture, Hyper1ConnectorAsHyper014ConnectorFuture,
+    };
+
     cfg_service! {
         pub use crate::http_service::{
             Hyper1HttpServiceAsTowerService03HttpServiceFuture,
             TowerService03HttpServiceAsHyper1HttpServiceFuture,
         };
+        pub use crate::make_service::TowerService03MakeServiceAsHyper1MakeServiceFuture;
         pub use crate::service::TowerService03ServiceAsHyper1ServiceFuture;
+        #[cfg(feature = "buffer")]
+        pub use crate::buffer::BufferedFuture;
     }
 }
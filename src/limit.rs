@@ -0,0 +1,177 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+use http::{Request, Response, StatusCode};
+use pin_project_lite::pin_project;
+
+use crate::{HttpBody1ToHttpBody04, Strict};
+
+/// A [tower-service 0.3 `Service`] that limits the size of request bodies converted from
+/// http-body 1.0.
+///
+/// Requests whose size hint, usually from the `Content-Length` header, exceeds the limit are
+/// rejected with `413 Payload Too Large` without calling the inner service. The response body is
+/// created with `Default::default()`.
+///
+/// Other request bodies, such as chunked bodies, are passed to the inner service using the
+/// [`Strict`] policy with [`Strict::limit`], so reading more than the limit fails with
+/// [`Error::LengthLimitExceeded`]. This enforces the limit for every route of the inner service.
+/// If the limit is exceeded before the inner service has produced a response, that response is
+/// replaced with `413 Payload Too Large` as well, regardless of how the inner service handled the
+/// error. Responses produced before the limit is exceeded, such as streaming responses that read
+/// the request body while responding, are passed through unchanged.
+///
+/// See also [`TowerService03HttpServiceAsHyper1HttpService::request_body_limit`].
+///
+/// [tower-service 0.3 `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
+/// [`Error::LengthLimitExceeded`]: crate::Error::LengthLimitExceeded
+/// [`TowerService03HttpServiceAsHyper1HttpService::request_body_limit`]: crate::TowerService03HttpServiceAsHyper1HttpService::request_body_limit
+#[derive(Clone, Copy, Debug)]
+pub struct RequestBodyLimit<S> {
+    service: S,
+    limit: usize,
+}
+
+impl<S> RequestBodyLimit<S> {
+    /// Create a new `RequestBodyLimit`.
+    #[inline]
+    pub fn new(service: S, limit: usize) -> Self {
+        Self { service, limit }
+    }
+}
+
+impl<S, ReqBody, ResBody> tower_service_03::Service<Request<HttpBody1ToHttpBody04<ReqBody>>>
+    for RequestBodyLimit<S>
+where
+    S: tower_service_03::Service<
+        Request<HttpBody1ToHttpBody04<ReqBody, Strict>>,
+        Response = Response<ResBody>,
+    >,
+    ReqBody: http_body_1::Body,
+    ResBody: Default,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = RequestBodyLimitFuture<S::Future, ResBody>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<HttpBody1ToHttpBody04<ReqBody>>) -> Self::Future {
+        let size_hint = http_body_04::Body::size_hint(req.body());
+        if size_hint.lower() > self.limit as u64 {
+            return RequestBodyLimitFuture {
+                kind: Kind::PayloadTooLarge {
+                    response: Some(payload_too_large()),
+                },
+            };
+        }
+
+        let limit_exceeded = Arc::new(AtomicBool::new(false));
+        let policy = Strict::new()
+            .limit(self.limit)
+            .notify_limit_exceeded(limit_exceeded.clone());
+        let req = req.map(|body| body.on_unexpected_frame(policy));
+        RequestBodyLimitFuture {
+            kind: Kind::Future {
+                future: self.service.call(req),
+                limit_exceeded,
+            },
+        }
+    }
+}
+
+pin_project! {
+    /// Response future for [`RequestBodyLimit`].
+    #[derive(Debug)]
+    pub struct RequestBodyLimitFuture<F, B> {
+        #[pin]
+        kind: Kind<F, B>,
+    }
+}
+
+pin_project! {
+    #[project = KindProj]
+    #[derive(Debug)]
+    enum Kind<F, B> {
+        Future {
+            #[pin]
+            future: F,
+            limit_exceeded: Arc<AtomicBool>,
+        },
+        PayloadTooLarge {
+            response: Option<Response<B>>,
+        },
+    }
+}
+
+impl<F, B, E> Future for RequestBodyLimitFuture<F, B>
+where
+    F: Future<Output = Result<Response<B>, E>>,
+    B: Default,
+{
+    type Output = Result<Response<B>, E>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project().kind.project() {
+            KindProj::Future {
+                future,
+                limit_exceeded,
+            } => {
+                let res = ready!(future.poll(cx))?;
+                if limit_exceeded.load(Ordering::Relaxed) {
+                    Poll::Ready(Ok(payload_too_large()))
+                } else {
+                    Poll::Ready(Ok(res))
+                }
+            }
+            KindProj::PayloadTooLarge { response } => {
+                Poll::Ready(Ok(response.take().expect("future polled after completion")))
+            }
+        }
+    }
+}
+
+fn payload_too_large<B>() -> Response<B>
+where
+    B: Default,
+{
+    let mut res = Response::new(B::default());
+    *res.status_mut() = StatusCode::PAYLOAD_TOO_LARGE;
+    res
+}
+
+/// A [tower-layer 0.3 `Layer`] that produces [`RequestBodyLimit`]s.
+///
+/// [tower-layer 0.3 `Layer`]: https://docs.rs/tower-layer/latest/tower_layer/trait.Layer.html
+#[derive(Clone, Copy, Debug)]
+pub struct RequestBodyLimitLayer {
+    limit: usize,
+}
+
+impl RequestBodyLimitLayer {
+    /// Create a new `RequestBodyLimitLayer`.
+    #[inline]
+    pub fn new(limit: usize) -> Self {
+        Self { limit }
+    }
+}
+
+impl<S> tower_layer_03::Layer<S> for RequestBodyLimitLayer {
+    type Service = RequestBodyLimit<S>;
+
+    #[inline]
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestBodyLimit::new(inner, self.limit)
+    }
+}
@@ -0,0 +1,163 @@
+use std::{
+    fmt::Debug,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project_lite::pin_project;
+use tower::{util::Oneshot, ServiceExt};
+
+use crate::TowerService03HttpServiceAsHyper1HttpService;
+
+/// Converts a [tower-service 0.3 `MakeService`] to a hyper 1.0 `Service` that produces
+/// [`TowerService03HttpServiceAsHyper1HttpService`]s.
+///
+/// hyper 0.14's `Server` calls a `MakeService` with the accepted connection to create a service
+/// for each connection. hyper 1.0 has no such concept, so this adapter lets an accept loop do the
+/// same thing: call it with information about the connection (the "target") and serve the
+/// connection with the service it returns.
+///
+/// This keeps per-connection state such as axum's `ConnectInfo` working after migrating to
+/// hyper 1.0, with one caveat: axum 0.6 only implements `Connected` for `SocketAddr` with hyper
+/// 0.14's `&AddrStream` as the target, so `into_make_service_with_connect_info::<SocketAddr>()`
+/// doesn't work with other targets and orphan rules prevent adding the missing impl. Instead
+/// wrap the address in a type of your own that implements `Connected` for the target, like
+/// `ClientAddr` in the example below, and extract `ConnectInfo<ClientAddr>`.
+///
+/// # Example
+///
+/// ```no_run
+/// # use hyper_1 as hyper;
+/// use axum::{extract::connect_info::{ConnectInfo, Connected}, routing::get, Router};
+/// use hyper::{server::conn::http1, service::Service};
+/// use std::net::SocketAddr;
+/// use tokio::net::TcpListener;
+/// use tower_hyper_http_body_compat::{
+///     TokioIoToHyper1Io, TowerService03MakeServiceAsHyper1MakeService,
+/// };
+///
+/// #[derive(Clone, Debug)]
+/// struct ClientAddr(SocketAddr);
+///
+/// impl Connected<SocketAddr> for ClientAddr {
+///     fn connect_info(target: SocketAddr) -> Self {
+///         ClientAddr(target)
+///     }
+/// }
+///
+/// async fn handler(ConnectInfo(ClientAddr(addr)): ConnectInfo<ClientAddr>) -> String {
+///     format!("Hello {addr}")
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///     let app = Router::new().route("/", get(handler));
+///
+///     let make_service = TowerService03MakeServiceAsHyper1MakeService::new(
+///         app.into_make_service_with_connect_info::<ClientAddr>(),
+///     );
+///
+///     let tcp_listener = TcpListener::bind("0.0.0.0:8080").await?;
+///     loop {
+///         let (tcp_stream, remote_addr) = tcp_listener.accept().await?;
+///         let service = make_service.call(remote_addr).await?;
+///
+///         tokio::task::spawn(async move {
+///             if let Err(http_err) = http1::Builder::new()
+///                     .serve_connection(TokioIoToHyper1Io::new(tcp_stream), service)
+///                     .await {
+///                 eprintln!("Error while serving HTTP connection: {}", http_err);
+///             }
+///         });
+///     }
+/// }
+/// ```
+///
+/// [tower-service 0.3 `MakeService`]: https://docs.rs/tower/latest/tower/make/trait.MakeService.html
+pub struct TowerService03MakeServiceAsHyper1MakeService<M, B> {
+    make_service: M,
+    _marker: PhantomData<fn() -> B>,
+}
+
+impl<M, B> TowerService03MakeServiceAsHyper1MakeService<M, B> {
+    /// Create a new `TowerService03MakeServiceAsHyper1MakeService`.
+    #[inline]
+    pub fn new(make_service: M) -> Self {
+        Self {
+            make_service,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<M, B> Copy for TowerService03MakeServiceAsHyper1MakeService<M, B> where M: Copy {}
+
+impl<M, B> Clone for TowerService03MakeServiceAsHyper1MakeService<M, B>
+where
+    M: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            make_service: self.make_service.clone(),
+            _marker: self._marker,
+        }
+    }
+}
+
+impl<M, B> Debug for TowerService03MakeServiceAsHyper1MakeService<M, B>
+where
+    M: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TowerService03MakeServiceAsHyper1MakeService")
+            .field("make_service", &self.make_service)
+            .finish()
+    }
+}
+
+impl<M, B, T> hyper_1::service::Service<T> for TowerService03MakeServiceAsHyper1MakeService<M, B>
+where
+    M: tower_service_03::Service<T> + Clone,
+{
+    type Response = TowerService03HttpServiceAsHyper1HttpService<M::Response, B>;
+    type Error = M::Error;
+    type Future = TowerService03MakeServiceAsHyper1MakeServiceFuture<M, T, B>;
+
+    #[inline]
+    fn call(&self, target: T) -> Self::Future {
+        TowerService03MakeServiceAsHyper1MakeServiceFuture {
+            // have to drive backpressure in the future
+            future: self.make_service.clone().oneshot(target),
+            _marker: PhantomData,
+        }
+    }
+}
+
+pin_project! {
+    /// Response future for [`TowerService03MakeServiceAsHyper1MakeService`].
+    pub struct TowerService03MakeServiceAsHyper1MakeServiceFuture<M, T, B>
+    where
+        M: tower_service_03::Service<T>,
+    {
+        #[pin]
+        future: Oneshot<M, T>,
+        _marker: PhantomData<fn() -> B>,
+    }
+}
+
+impl<M, T, B> Future for TowerService03MakeServiceAsHyper1MakeServiceFuture<M, T, B>
+where
+    M: tower_service_03::Service<T>,
+{
+    type Output = Result<TowerService03HttpServiceAsHyper1HttpService<M::Response, B>, M::Error>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let service = ready!(self.project().future.poll(cx))?;
+        Poll::Ready(Ok(TowerService03HttpServiceAsHyper1HttpService::new(
+            service,
+        )))
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use pin_project_lite::pin_project;
+
+/// A [tower-service 0.3 `Service`] that converts errors from the inner service into
+/// [`tower::BoxError`]s.
+///
+/// See also [`TowerService03HttpServiceAsHyper1HttpService::box_error`].
+///
+/// [tower-service 0.3 `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
+/// [`TowerService03HttpServiceAsHyper1HttpService::box_error`]: crate::TowerService03HttpServiceAsHyper1HttpService::box_error
+#[derive(Clone, Copy, Debug)]
+pub struct MapErrToBoxError<S> {
+    service: S,
+}
+
+impl<S> MapErrToBoxError<S> {
+    /// Create a new `MapErrToBoxError`.
+    #[inline]
+    pub fn new(service: S) -> Self {
+        Self { service }
+    }
+}
+
+impl<S, R> tower_service_03::Service<R> for MapErrToBoxError<S>
+where
+    S: tower_service_03::Service<R>,
+    S::Error: Into<tower::BoxError>,
+{
+    type Response = S::Response;
+    type Error = tower::BoxError;
+    type Future = MapErrToBoxErrorFuture<S::Future>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx).map_err(Into::into)
+    }
+
+    #[inline]
+    fn call(&mut self, req: R) -> Self::Future {
+        MapErrToBoxErrorFuture {
+            future: self.service.call(req),
+        }
+    }
+}
+
+pin_project! {
+    /// Response future for [`MapErrToBoxError`].
+    #[derive(Debug)]
+    pub struct MapErrToBoxErrorFuture<F> {
+        #[pin]
+        future: F,
+    }
+}
+
+impl<F, T, E> Future for MapErrToBoxErrorFuture<F>
+where
+    F: Future<Output = Result<T, E>>,
+    E: Into<tower::BoxError>,
+{
+    type Output = Result<T, tower::BoxError>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().future.poll(cx).map_err(Into::into)
+    }
+}
+
+/// A [tower-layer 0.3 `Layer`] that produces [`MapErrToBoxError`]s.
+///
+/// [tower-layer 0.3 `Layer`]: https://docs.rs/tower-layer/latest/tower_layer/trait.Layer.html
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MapErrToBoxErrorLayer {
+    _priv: (),
+}
+
+impl MapErrToBoxErrorLayer {
+    /// Create a new `MapErrToBoxErrorLayer`.
+    #[inline]
+    pub fn new() -> Self {
+        Self { _priv: () }
+    }
+}
+
+impl<S> tower_layer_03::Layer<S> for MapErrToBoxErrorLayer {
+    type Service = MapErrToBoxError<S>;
+
+    #[inline]
+    fn layer(&self, inner: S) -> Self::Service {
+        MapErrToBoxError::new(inner)
+    }
+}
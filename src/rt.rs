@@ -0,0 +1,141 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use pin_project_lite::pin_project;
+use tokio::runtime::Handle;
+
+/// A [hyper 1.0 `Executor`] that spawns futures onto a tokio runtime.
+///
+/// This is required by hyper 1.0's `http2::Builder`. By default futures are spawned onto the
+/// runtime of the calling task. Use [`TokioExecutor::with_handle`] to spawn onto a specific
+/// runtime.
+///
+/// [hyper 1.0 `Executor`]: https://docs.rs/hyper/1/hyper/rt/trait.Executor.html
+#[derive(Clone, Debug, Default)]
+pub struct TokioExecutor {
+    handle: Option<Handle>,
+}
+
+impl TokioExecutor {
+    /// Create a new `TokioExecutor` that spawns futures onto the current runtime.
+    #[inline]
+    pub fn new() -> Self {
+        Self { handle: None }
+    }
+
+    /// Create a new `TokioExecutor` that spawns futures onto the runtime of `handle`.
+    #[inline]
+    pub fn with_handle(handle: Handle) -> Self {
+        Self {
+            handle: Some(handle),
+        }
+    }
+}
+
+impl From<Handle> for TokioExecutor {
+    #[inline]
+    fn from(handle: Handle) -> Self {
+        Self::with_handle(handle)
+    }
+}
+
+impl<F> hyper_1::rt::Executor<F> for TokioExecutor
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    #[inline]
+    fn execute(&self, fut: F) {
+        match &self.handle {
+            Some(handle) => {
+                handle.spawn(fut);
+            }
+            None => {
+                tokio::spawn(fut);
+            }
+        }
+    }
+}
+
+/// Converts a [hyper 0.14 `Executor`] to a [hyper 1.0 `Executor`].
+///
+/// [hyper 0.14 `Executor`]: https://docs.rs/hyper/0.14/hyper/rt/trait.Executor.html
+/// [hyper 1.0 `Executor`]: https://docs.rs/hyper/1/hyper/rt/trait.Executor.html
+#[cfg(feature = "hyper-014")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Hyper014ExecutorAsHyper1Executor<E>(E);
+
+#[cfg(feature = "hyper-014")]
+impl<E> Hyper014ExecutorAsHyper1Executor<E> {
+    /// Create a new `Hyper014ExecutorAsHyper1Executor`.
+    #[inline]
+    pub fn new(inner: E) -> Self {
+        Self(inner)
+    }
+}
+
+#[cfg(feature = "hyper-014")]
+impl<E, F> hyper_1::rt::Executor<F> for Hyper014ExecutorAsHyper1Executor<E>
+where
+    E: hyper_014::rt::Executor<F>,
+{
+    #[inline]
+    fn execute(&self, fut: F) {
+        self.0.execute(fut)
+    }
+}
+
+/// A [hyper 1.0 `Timer`] backed by tokio's timer.
+///
+/// This is required for hyper 1.0's timeouts, such as `http1::Builder::header_read_timeout` or
+/// `http2::Builder::keep_alive_interval`.
+///
+/// [hyper 1.0 `Timer`]: https://docs.rs/hyper/1/hyper/rt/trait.Timer.html
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TokioTimer {
+    _priv: (),
+}
+
+impl TokioTimer {
+    /// Create a new `TokioTimer`.
+    #[inline]
+    pub fn new() -> Self {
+        Self { _priv: () }
+    }
+}
+
+impl hyper_1::rt::Timer for TokioTimer {
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn hyper_1::rt::Sleep>> {
+        Box::pin(TokioSleep {
+            inner: tokio::time::sleep(duration),
+        })
+    }
+
+    fn sleep_until(&self, deadline: Instant) -> Pin<Box<dyn hyper_1::rt::Sleep>> {
+        Box::pin(TokioSleep {
+            inner: tokio::time::sleep_until(deadline.into()),
+        })
+    }
+}
+
+pin_project! {
+    struct TokioSleep {
+        #[pin]
+        inner: tokio::time::Sleep,
+    }
+}
+
+impl Future for TokioSleep {
+    type Output = ();
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx)
+    }
+}
+
+impl hyper_1::rt::Sleep for TokioSleep {}
@@ -0,0 +1,728 @@
+//! Serving tower-service 0.3 HTTP services with hyper 1.0.
+//!
+//! hyper 1.0 doesn't have a `Server` like hyper 0.14 did. Instead the caller accepts connections
+//! and serves each of them with hyper's `http1::Builder` or `http2::Builder`, as in the
+//! [crate level example](crate). This module provides that accept loop, including graceful
+//! shutdown, and a [`Server`] with the same API as hyper 0.14's.
+//!
+//! Connections are served with [`TowerService03HttpServiceAsHyper1HttpService`] and the
+//! [`ConnectionInfo`] of each connection is inserted into the extensions of its requests. With
+//! both the `http1` and `http2` features enabled, the protocol is detected from the first bytes
+//! sent by the client. Otherwise the enabled protocol is used. HTTP/1 connections support
+//! upgrades.
+//!
+//! Errors from accepting or serving connections are ignored, as they are by hyper 0.14's
+//! `Server`.
+//!
+//! This module requires the `server` and `tokio` features, and `http1` or `http2`.
+//!
+//! [`TowerService03HttpServiceAsHyper1HttpService`]: crate::TowerService03HttpServiceAsHyper1HttpService
+
+use std::{
+    convert::Infallible,
+    future::{poll_fn, Future, IntoFuture},
+    io,
+    net::SocketAddr,
+    pin::{pin, Pin},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use http::{Request, Response};
+use hyper_1::body::Incoming;
+#[cfg(feature = "http1")]
+use hyper_1::server::conn::http1;
+#[cfg(feature = "http2")]
+use hyper_1::server::conn::http2;
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    net::{TcpListener, TcpStream},
+    sync::watch,
+};
+use tower::{BoxError, ServiceExt};
+
+#[cfg(feature = "http2")]
+use crate::TokioExecutor;
+use crate::{
+    AddConnectionInfo, ConnectionInfo, HttpBody1ToHttpBody04, TokioIoToHyper1Io, TokioTimer,
+    TowerService03HttpServiceAsHyper1HttpService,
+};
+
+/// Serve connections accepted from `listener` with `service` until `signal` completes, then shut
+/// down gracefully.
+///
+/// Once `signal` completes, `listener` is closed and every connection is shut down gracefully:
+/// idle HTTP/1 connections are closed, HTTP/2 clients are sent a `GOAWAY` frame, and requests
+/// that are already being processed run to completion. The returned future resolves once all
+/// connections have been closed.
+///
+/// This replaces hyper 0.14's `Server::with_graceful_shutdown`.
+///
+/// # Example
+///
+/// ```no_run
+/// use http::{Request, Response};
+/// use std::convert::Infallible;
+/// use tokio::net::TcpListener;
+/// use tower_hyper_http_body_compat::server;
+///
+/// async fn handler<B>(_req: Request<B>) -> Result<Response<hyper_014::Body>, Infallible> {
+///     Ok(Response::new(hyper_014::Body::from("Hello, World!")))
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///     let tcp_listener = TcpListener::bind("0.0.0.0:8080").await?;
+///
+///     let signal = async {
+///         tokio::signal::ctrl_c()
+///             .await
+///             .expect("failed to install CTRL+C handler");
+///     };
+///     server::serve_with_graceful_shutdown(tcp_listener, tower::service_fn(handler), signal)
+///         .await;
+///
+///     Ok(())
+/// }
+/// ```
+pub async fn serve_with_graceful_shutdown<S, ResBody, F>(
+    listener: TcpListener,
+    service: S,
+    signal: F,
+) where
+    S: tower_service_03::Service<
+            Request<HttpBody1ToHttpBody04<Incoming>>,
+            Response = Response<ResBody>,
+        > + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+    S::Error: Into<BoxError>,
+    ResBody: http_body_04::Body + Send + 'static,
+    ResBody::Data: Send,
+    ResBody::Error: Into<BoxError>,
+    F: Future<Output = ()>,
+{
+    let make_service =
+        tower::service_fn(|_| std::future::ready(Ok::<_, Infallible>(service.clone())));
+    match Config::new().run(listener, make_service, signal).await {
+        Ok(()) => {}
+        Err(err) => match err {},
+    }
+}
+
+/// A listening HTTP server that mimics hyper 0.14's `Server`.
+///
+/// Migrating from hyper 0.14 mostly requires replacing `hyper::Server` with this type:
+///
+/// ```no_run
+/// use axum::{routing::get, Router};
+/// use std::net::SocketAddr;
+/// use tower_hyper_http_body_compat::server::Server;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///     let app = Router::new().route("/", get(|| async { "Hello, World!" }));
+///
+///     let addr = SocketAddr::from(([0, 0, 0, 0], 8080));
+///     Server::bind(&addr)
+///         .tcp_nodelay(true)
+///         .serve(app.into_make_service())
+///         .await?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// The `MakeService` is called with the [`ConnectionInfo`] of each connection and the service it
+/// returns is served with [`TowerService03HttpServiceAsHyper1HttpService`]. The `ConnectionInfo`
+/// is also inserted into the extensions of every request.
+///
+/// hyper 0.14's `Server` calls the `MakeService` with `&AddrStream` instead, so
+/// `app.into_make_service_with_connect_info::<SocketAddr>()` doesn't compile with this type:
+/// axum 0.6 only implements `Connected` for `SocketAddr` with `&AddrStream` as the target, and
+/// orphan rules prevent adding the impl for `ConnectionInfo`. Either wrap the address in a type
+/// of your own that implements `Connected<ConnectionInfo>` and extract `ConnectInfo` of that type:
+///
+/// ```no_run
+/// use axum::{
+///     extract::connect_info::{ConnectInfo, Connected},
+///     routing::get,
+///     Router,
+/// };
+/// use std::net::SocketAddr;
+/// use tower_hyper_http_body_compat::{server::Server, ConnectionInfo};
+///
+/// #[derive(Clone, Debug)]
+/// struct ClientAddr(SocketAddr);
+///
+/// impl Connected<ConnectionInfo> for ClientAddr {
+///     fn connect_info(target: ConnectionInfo) -> Self {
+///         ClientAddr(target.remote_addr())
+///     }
+/// }
+///
+/// async fn handler(ConnectInfo(ClientAddr(addr)): ConnectInfo<ClientAddr>) -> String {
+///     format!("Hello {addr}")
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///     let app = Router::new().route("/", get(handler));
+///
+///     let addr = SocketAddr::from(([0, 0, 0, 0], 8080));
+///     Server::bind(&addr)
+///         .serve(app.into_make_service_with_connect_info::<ClientAddr>())
+///         .await?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// or keep using `into_make_service()` and extract `Extension<ConnectionInfo>`, since the
+/// `ConnectionInfo` is in the extensions of every request:
+///
+/// ```no_run
+/// use axum::{routing::get, Extension, Router};
+/// use std::net::SocketAddr;
+/// use tower_hyper_http_body_compat::{server::Server, ConnectionInfo};
+///
+/// async fn handler(Extension(info): Extension<ConnectionInfo>) -> String {
+///     format!("Hello {}", info.remote_addr())
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///     let app = Router::new().route("/", get(handler));
+///
+///     let addr = SocketAddr::from(([0, 0, 0, 0], 8080));
+///     Server::bind(&addr).serve(app.into_make_service()).await?;
+///
+///     Ok(())
+/// }
+/// ```
+///
+/// `Server` implements `IntoFuture` and runs until an error occurs while making a service. Use
+/// [`Server::with_graceful_shutdown`] to stop it.
+///
+/// [`TowerService03HttpServiceAsHyper1HttpService`]: crate::TowerService03HttpServiceAsHyper1HttpService
+#[derive(Debug)]
+pub struct Server<M> {
+    builder: Builder,
+    make_service: M,
+}
+
+impl Server<()> {
+    /// Bind to `addr` and return a [`Builder`] for configuring the server.
+    ///
+    /// # Panics
+    ///
+    /// Panics if binding to `addr` fails. Use [`Server::try_bind`] to handle the error.
+    pub fn bind(addr: &SocketAddr) -> Builder {
+        Self::try_bind(addr).unwrap_or_else(|err| panic!("error binding to {addr}: {err}"))
+    }
+
+    /// Bind to `addr` and return a [`Builder`] for configuring the server.
+    pub fn try_bind(addr: &SocketAddr) -> io::Result<Builder> {
+        Self::from_tcp(std::net::TcpListener::bind(addr)?)
+    }
+
+    /// Create a [`Builder`] from an already bound listener.
+    pub fn from_tcp(listener: std::net::TcpListener) -> io::Result<Builder> {
+        listener.set_nonblocking(true)?;
+        Ok(Builder {
+            listener,
+            config: Config::new(),
+        })
+    }
+}
+
+impl<M> Server<M> {
+    /// The local address the server is listening on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.builder
+            .listener
+            .local_addr()
+            .expect("listener has a local address")
+    }
+
+    /// Stop accepting connections once `signal` completes and shut down gracefully.
+    ///
+    /// See [`serve_with_graceful_shutdown`] for details.
+    pub fn with_graceful_shutdown<F>(self, signal: F) -> Graceful<M, F>
+    where
+        F: Future<Output = ()>,
+    {
+        Graceful {
+            server: self,
+            signal,
+        }
+    }
+}
+
+impl<M, S, ResBody> IntoFuture for Server<M>
+where
+    M: tower_service_03::Service<ConnectionInfo, Response = S> + Send + 'static,
+    M::Future: Send,
+    M::Error: Into<BoxError>,
+    S: tower_service_03::Service<
+            Request<HttpBody1ToHttpBody04<Incoming>>,
+            Response = Response<ResBody>,
+        > + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+    S::Error: Into<BoxError>,
+    ResBody: http_body_04::Body + Send + 'static,
+    ResBody::Data: Send,
+    ResBody::Error: Into<BoxError>,
+{
+    type Output = Result<(), BoxError>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        self.with_graceful_shutdown(std::future::pending())
+            .into_future()
+    }
+}
+
+/// A builder for a [`Server`], created with [`Server::bind`].
+#[derive(Debug)]
+pub struct Builder {
+    listener: std::net::TcpListener,
+    config: Config,
+}
+
+impl Builder {
+    /// Set whether HTTP/1 connections should support keep-alive.
+    ///
+    /// Default is `true`.
+    #[cfg(feature = "http1")]
+    pub fn http1_keepalive(mut self, val: bool) -> Self {
+        self.config.http1.keep_alive(val);
+        self
+    }
+
+    /// Set whether connections should only be served with HTTP/2.
+    ///
+    /// Default is `false`, meaning the protocol is detected from the first bytes sent by the
+    /// client if the `http1` feature is enabled.
+    #[cfg(feature = "http2")]
+    pub fn http2_only(mut self, val: bool) -> Self {
+        self.config.protocol = if val {
+            Protocol::Http2
+        } else {
+            Protocol::default()
+        };
+        self
+    }
+
+    /// Set whether `TCP_NODELAY` should be set on accepted connections.
+    ///
+    /// Default is `false`.
+    pub fn tcp_nodelay(mut self, val: bool) -> Self {
+        self.config.tcp_nodelay = val;
+        self
+    }
+
+    /// Serve connections with services created by `make_service`.
+    pub fn serve<M>(self, make_service: M) -> Server<M> {
+        Server {
+            builder: self,
+            make_service,
+        }
+    }
+}
+
+/// A [`Server`] that shuts down gracefully, created with [`Server::with_graceful_shutdown`].
+#[derive(Debug)]
+pub struct Graceful<M, F> {
+    server: Server<M>,
+    signal: F,
+}
+
+impl<M, S, ResBody, F> IntoFuture for Graceful<M, F>
+where
+    M: tower_service_03::Service<ConnectionInfo, Response = S> + Send + 'static,
+    M::Future: Send,
+    M::Error: Into<BoxError>,
+    S: tower_service_03::Service<
+            Request<HttpBody1ToHttpBody04<Incoming>>,
+            Response = Response<ResBody>,
+        > + Clone
+        + Send
+        + 'static,
+    S::Future: Send + 'static,
+    S::Error: Into<BoxError>,
+    ResBody: http_body_04::Body + Send + 'static,
+    ResBody::Data: Send,
+    ResBody::Error: Into<BoxError>,
+    F: Future<Output = ()> + Send + 'static,
+{
+    type Output = Result<(), BoxError>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        let Server {
+            builder: Builder { listener, config },
+            make_service,
+        } = self.server;
+        let signal = self.signal;
+        Box::pin(async move {
+            let listener = TcpListener::from_std(listener)?;
+            let make_service = make_service.map_err(Into::<BoxError>::into);
+            config.run(listener, make_service, signal).await
+        })
+    }
+}
+
+/// The protocols connections are served with.
+#[derive(Clone, Copy, Debug)]
+enum Protocol {
+    #[cfg(feature = "http1")]
+    Http1,
+    #[cfg(feature = "http2")]
+    Http2,
+    #[cfg(all(feature = "http1", feature = "http2"))]
+    Auto,
+}
+
+impl Default for Protocol {
+    fn default() -> Self {
+        #[cfg(all(feature = "http1", feature = "http2"))]
+        return Protocol::Auto;
+        #[cfg(all(feature = "http1", not(feature = "http2")))]
+        return Protocol::Http1;
+        #[cfg(all(feature = "http2", not(feature = "http1")))]
+        return Protocol::Http2;
+    }
+}
+
+/// How connections are served.
+#[derive(Clone, Debug)]
+struct Config {
+    #[cfg(feature = "http1")]
+    http1: http1::Builder,
+    #[cfg(feature = "http2")]
+    http2: http2::Builder<TokioExecutor>,
+    protocol: Protocol,
+    tcp_nodelay: bool,
+}
+
+impl Config {
+    fn new() -> Self {
+        #[cfg(feature = "http1")]
+        let mut http1 = http1::Builder::new();
+        #[cfg(feature = "http1")]
+        http1.timer(TokioTimer::new());
+
+        #[cfg(feature = "http2")]
+        let mut http2 = http2::Builder::new(TokioExecutor::new());
+        #[cfg(feature = "http2")]
+        http2.timer(TokioTimer::new());
+
+        Self {
+            #[cfg(feature = "http1")]
+            http1,
+            #[cfg(feature = "http2")]
+            http2,
+            protocol: Protocol::default(),
+            tcp_nodelay: false,
+        }
+    }
+
+    /// Accept connections until `signal` completes and serve each of them with a service created
+    /// by `make_service`, then wait for the connections to shut down.
+    async fn run<M, S, ResBody, F>(
+        &self,
+        listener: TcpListener,
+        mut make_service: M,
+        signal: F,
+    ) -> Result<(), M::Error>
+    where
+        M: tower_service_03::Service<ConnectionInfo, Response = S>,
+        S: tower_service_03::Service<
+                Request<HttpBody1ToHttpBody04<Incoming>>,
+                Response = Response<ResBody>,
+            > + Clone
+            + Send
+            + 'static,
+        S::Future: Send + 'static,
+        S::Error: Into<BoxError>,
+        ResBody: http_body_04::Body + Send + 'static,
+        ResBody::Data: Send,
+        ResBody::Error: Into<BoxError>,
+        F: Future<Output = ()>,
+    {
+        let (shutdown_tx, shutdown_rx) = watch::channel(());
+        let mut signal = pin!(signal);
+
+        let result = loop {
+            let accepted = poll_fn(|cx| {
+                if signal.as_mut().poll(cx).is_ready() {
+                    return Poll::Ready(None);
+                }
+                listener.poll_accept(cx).map(Some)
+            })
+            .await;
+
+            let (stream, remote_addr) = match accepted {
+                Some(Ok(accepted)) => accepted,
+                Some(Err(err)) if is_connection_error(&err) => continue,
+                Some(Err(_)) => {
+                    // such as running out of file descriptors, give connections time to close
+                    let mut sleep = pin!(tokio::time::sleep(Duration::from_secs(1)));
+                    let signalled = poll_fn(|cx| {
+                        if signal.as_mut().poll(cx).is_ready() {
+                            return Poll::Ready(true);
+                        }
+                        sleep.as_mut().poll(cx).map(|()| false)
+                    })
+                    .await;
+                    if signalled {
+                        break Ok(());
+                    }
+                    continue;
+                }
+                None => break Ok(()),
+            };
+
+            let Ok(local_addr) = stream.local_addr() else {
+                continue;
+            };
+            if self.tcp_nodelay {
+                let _ = stream.set_nodelay(true);
+            }
+            let info = ConnectionInfo::new(remote_addr, local_addr);
+            let service = match make_service.ready().await {
+                Ok(make_service) => make_service.call(info).await,
+                Err(err) => Err(err),
+            };
+            let service = match service {
+                Ok(service) => service,
+                Err(err) => break Err(err),
+            };
+            let service =
+                TowerService03HttpServiceAsHyper1HttpService::new(service).connection_info(info);
+
+            tokio::spawn(
+                self.clone()
+                    .serve_connection(stream, service, shutdown_rx.clone()),
+            );
+        };
+
+        drop(listener);
+        drop(shutdown_rx);
+        shutdown_tx.send_replace(());
+        shutdown_tx.closed().await;
+
+        result
+    }
+
+    async fn serve_connection<S, ResBody>(
+        self,
+        stream: TcpStream,
+        service: TowerService03HttpServiceAsHyper1HttpService<
+            AddConnectionInfo<S, ConnectionInfo>,
+            HttpBody1ToHttpBody04<Incoming>,
+        >,
+        mut shutdown: watch::Receiver<()>,
+    ) where
+        S: tower_service_03::Service<
+                Request<HttpBody1ToHttpBody04<Incoming>>,
+                Response = Response<ResBody>,
+            > + Clone
+            + Send
+            + 'static,
+        S::Future: Send + 'static,
+        S::Error: Into<BoxError>,
+        ResBody: http_body_04::Body + Send + 'static,
+        ResBody::Data: Send,
+        ResBody::Error: Into<BoxError>,
+    {
+        let mut shutdown = pin!(async move {
+            // an error means the server is gone which is a shutdown as well
+            let _ = shutdown.changed().await;
+        });
+
+        let Some((protocol, prefix)) = self.detect_protocol(&stream, shutdown.as_mut()).await
+        else {
+            return;
+        };
+
+        let io = TokioIoToHyper1Io::new(Rewind {
+            prefix,
+            pos: 0,
+            stream,
+        });
+
+        match protocol {
+            #[cfg(feature = "http1")]
+            Protocol::Http1 => {
+                let conn = self.http1.serve_connection(io, service).with_upgrades();
+                drive(
+                    conn,
+                    http1::UpgradeableConnection::graceful_shutdown,
+                    shutdown,
+                )
+                .await;
+            }
+            #[cfg(feature = "http2")]
+            Protocol::Http2 => {
+                let conn = self.http2.serve_connection(io, service);
+                drive(conn, http2::Connection::graceful_shutdown, shutdown).await;
+            }
+            #[cfg(all(feature = "http1", feature = "http2"))]
+            Protocol::Auto => unreachable!("the protocol has been detected"),
+        }
+    }
+
+    /// Determine the protocol of a connection.
+    ///
+    /// Returns the bytes read from `stream` while doing so, or `None` if the connection should be
+    /// closed.
+    async fn detect_protocol<F>(
+        &self,
+        stream: &TcpStream,
+        shutdown: Pin<&mut F>,
+    ) -> Option<(Protocol, Vec<u8>)>
+    where
+        F: Future<Output = ()>,
+    {
+        #[cfg(all(feature = "http1", feature = "http2"))]
+        if let Protocol::Auto = self.protocol {
+            let mut shutdown = shutdown;
+            let mut read = pin!(read_http2_preface(stream));
+            let (is_http2, prefix) = poll_fn(|cx| {
+                if shutdown.as_mut().poll(cx).is_ready() {
+                    return Poll::Ready(None);
+                }
+                read.as_mut().poll(cx).map(Result::ok)
+            })
+            .await?;
+
+            let protocol = if is_http2 {
+                Protocol::Http2
+            } else {
+                Protocol::Http1
+            };
+            return Some((protocol, prefix));
+        }
+
+        #[cfg(not(all(feature = "http1", feature = "http2")))]
+        let _ = (stream, shutdown);
+
+        Some((self.protocol, Vec::new()))
+    }
+}
+
+/// Poll `conn` to completion, starting a graceful shutdown once `shutdown` completes.
+async fn drive<C, F>(conn: C, graceful_shutdown: fn(Pin<&mut C>), shutdown: Pin<&mut F>)
+where
+    C: Future,
+    F: Future<Output = ()>,
+{
+    let mut conn = pin!(conn);
+    let mut shutdown = Some(shutdown);
+    poll_fn(|cx| {
+        if let Some(signal) = &mut shutdown {
+            if signal.as_mut().poll(cx).is_ready() {
+                shutdown = None;
+                graceful_shutdown(conn.as_mut());
+            }
+        }
+        conn.as_mut().poll(cx).map(drop)
+    })
+    .await;
+}
+
+fn is_connection_error(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::ConnectionReset
+    )
+}
+
+#[cfg(all(feature = "http1", feature = "http2"))]
+const HTTP2_PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+
+/// Read from `stream` until it's clear whether it starts with the HTTP/2 connection preface.
+///
+/// Returns whether it does and the bytes read.
+#[cfg(all(feature = "http1", feature = "http2"))]
+async fn read_http2_preface(stream: &TcpStream) -> io::Result<(bool, Vec<u8>)> {
+    let mut buf = Vec::with_capacity(HTTP2_PREFACE.len());
+    let mut chunk = [0; HTTP2_PREFACE.len()];
+    while buf.len() < HTTP2_PREFACE.len() && HTTP2_PREFACE.starts_with(&buf) {
+        stream.readable().await?;
+        match stream.try_read(&mut chunk[..HTTP2_PREFACE.len() - buf.len()]) {
+            Ok(0) => break,
+            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok((buf == HTTP2_PREFACE, buf))
+}
+
+/// A `TcpStream` that first yields the bytes read while detecting the protocol.
+#[derive(Debug)]
+struct Rewind {
+    prefix: Vec<u8>,
+    pos: usize,
+    stream: TcpStream,
+}
+
+impl AsyncRead for Rewind {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        if this.pos < this.prefix.len() {
+            let n = buf.remaining().min(this.prefix.len() - this.pos);
+            buf.put_slice(&this.prefix[this.pos..this.pos + n]);
+            this.pos += n;
+            return Poll::Ready(Ok(()));
+        }
+        Pin::new(&mut this.stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for Rewind {
+    #[inline]
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write(cx, buf)
+    }
+
+    #[inline]
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    #[inline]
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_shutdown(cx)
+    }
+
+    #[inline]
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write_vectored(cx, bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.stream.is_write_vectored()
+    }
+}
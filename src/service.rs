@@ -6,8 +6,13 @@ use std::{
 };
 
 use pin_project_lite::pin_project;
+#[cfg(feature = "buffer")]
+use tower::BoxError;
 use tower::{util::Oneshot, ServiceExt};
 
+#[cfg(feature = "buffer")]
+use crate::Buffered;
+
 // --- tower-service 0.3 to hyper 1.0 ---
 
 /// Converts a [tower-service 0.3 `Service`] to a [hyper 1.0 `Service`].
@@ -15,8 +20,18 @@ use tower::{util::Oneshot, ServiceExt};
 /// If you have a service that uses [`http::Request`] and [`http::Response`] then you probaby need
 /// [`TowerService03HttpServiceAsHyper1HttpService`] instead of this.
 ///
+/// # Backpressure
+///
+/// hyper 1.0's `Service` trait doesn't have a `poll_ready` method so each call clones the inner
+/// service and drives its readiness on the clone. That works well for services whose clones
+/// share readiness state, but middleware that keeps it per instance, such as
+/// `tower::limit::RateLimit` or a custom `poll_ready` that reserves a slot, is bypassed.
+///
+/// Use [`TowerService03ServiceAsHyper1Service::buffered`] to instead drive backpressure on a
+/// single, long-lived instance of the service.
+///
 /// [tower-service 0.3 `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
-/// [hyper 1.0 `Service`]: https://docs.rs/hyper/1.0.0-rc.4/hyper/service/trait.Service.html
+/// [hyper 1.0 `Service`]: https://docs.rs/hyper/1/hyper/service/trait.Service.html
 /// [`TowerService03HttpServiceAsHyper1HttpService`]: crate::TowerService03HttpServiceAsHyper1HttpService
 #[derive(Clone, Copy, Debug)]
 pub struct TowerService03ServiceAsHyper1Service<S>(S);
@@ -28,6 +43,34 @@ impl<S> TowerService03ServiceAsHyper1Service<S> {
     }
 }
 
+#[cfg(feature = "buffer")]
+impl<S, R> TowerService03ServiceAsHyper1Service<Buffered<S, R>>
+where
+    S: tower_service_03::Service<R>,
+    S::Error: Into<BoxError>,
+{
+    /// Create a new `TowerService03ServiceAsHyper1Service` that drives backpressure on a single
+    /// instance of `inner`.
+    ///
+    /// `inner` is moved into a [`Buffered`] whose worker task calls `poll_ready` on it before
+    /// dispatching each request, so middleware that keeps readiness state per instance (such as
+    /// `RateLimit`) behaves as it would under hyper 0.14. `bound` is the maximum number of
+    /// requests that can be queued while `inner` isn't ready.
+    ///
+    /// Errors are boxed.
+    ///
+    /// This must be called from within a tokio runtime since it spawns the buffer's worker task.
+    pub fn buffered(inner: S, bound: usize) -> Self
+    where
+        S: Send + 'static,
+        S::Future: Send,
+        S::Error: Send + Sync,
+        R: Send + 'static,
+    {
+        Self::new(Buffered::new(inner, bound))
+    }
+}
+
 impl<S, R> hyper_1::service::Service<R> for TowerService03ServiceAsHyper1Service<S>
 where
     S: tower_service_03::Service<R> + Clone,
@@ -68,6 +111,31 @@ where
     }
 }
 
+/// A [tower-layer 0.3 `Layer`] that produces [`TowerService03ServiceAsHyper1Service`]s.
+///
+/// [tower-layer 0.3 `Layer`]: https://docs.rs/tower-layer/latest/tower_layer/trait.Layer.html
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TowerService03ServiceAsHyper1ServiceLayer {
+    _priv: (),
+}
+
+impl TowerService03ServiceAsHyper1ServiceLayer {
+    /// Create a new `TowerService03ServiceAsHyper1ServiceLayer`.
+    #[inline]
+    pub fn new() -> Self {
+        Self { _priv: () }
+    }
+}
+
+impl<S> tower_layer_03::Layer<S> for TowerService03ServiceAsHyper1ServiceLayer {
+    type Service = TowerService03ServiceAsHyper1Service<S>;
+
+    #[inline]
+    fn layer(&self, inner: S) -> Self::Service {
+        TowerService03ServiceAsHyper1Service::new(inner)
+    }
+}
+
 // --- hyper 1.0 to tower-service 0.3 ---
 
 /// Converts a [hyper 1.0 `Service`] to a [tower-service 0.3 `Service`].
@@ -75,35 +143,151 @@ where
 /// If you have a service that uses [`http::Request`] and [`http::Response`] then you probaby need
 /// [`Hyper1HttpServiceAsTowerService03HttpService`] instead of this.
 ///
+/// # Backpressure
+///
+/// hyper 1.0's `Service` trait doesn't have a `poll_ready` method so by default this service is
+/// always ready. Use [`Hyper1ServiceAsTowerService03Service::with_poll_ready`] to attach a
+/// readiness source such that tower middleware like `Buffer` or `LoadShed` can observe
+/// pressure from the hyper 1.0 service.
+///
 /// [tower-service 0.3 `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
-/// [hyper 1.0 `Service`]: https://docs.rs/hyper/1.0.0-rc.4/hyper/service/trait.Service.html
+/// [hyper 1.0 `Service`]: https://docs.rs/hyper/1/hyper/service/trait.Service.html
 /// [`Hyper1HttpServiceAsTowerService03HttpService`]: crate::Hyper1HttpServiceAsTowerService03HttpService
-#[derive(Clone, Copy, Debug)]
-pub struct Hyper1ServiceAsTowerService03Service<S>(S);
+#[derive(Clone, Copy)]
+pub struct Hyper1ServiceAsTowerService03Service<S, P = AlwaysReady> {
+    service: S,
+    poll_ready: P,
+}
 
 impl<S> Hyper1ServiceAsTowerService03Service<S> {
     /// Create a new `Hyper1ServiceAsTowerService03Service`.
     #[inline]
     pub fn new(inner: S) -> Self {
-        Self(inner)
+        Self {
+            service: inner,
+            poll_ready: AlwaysReady::new(),
+        }
+    }
+}
+
+impl<S, P> Hyper1ServiceAsTowerService03Service<S, P> {
+    /// Use `poll_ready` to determine when the service is ready to accept a request.
+    ///
+    /// `poll_ready` can be any [`PollReady`], such as a closure with the signature
+    /// `FnMut(&mut Context<'_>) -> Poll<Result<(), S::Error>>`.
+    pub fn with_poll_ready<T>(self, poll_ready: T) -> Hyper1ServiceAsTowerService03Service<S, T> {
+        Hyper1ServiceAsTowerService03Service {
+            service: self.service,
+            poll_ready,
+        }
+    }
+}
+
+impl<S, P> Debug for Hyper1ServiceAsTowerService03Service<S, P>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Hyper1ServiceAsTowerService03Service")
+            .field("service", &self.service)
+            .finish()
     }
 }
 
-impl<S, R> tower_service_03::Service<R> for Hyper1ServiceAsTowerService03Service<S>
+impl<S, P, R> tower_service_03::Service<R> for Hyper1ServiceAsTowerService03Service<S, P>
 where
     S: hyper_1::service::Service<R>,
+    P: PollReady<S::Error>,
 {
     type Response = S::Response;
     type Error = S::Error;
     type Future = S::Future;
 
     #[inline]
-    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        Poll::Ready(Ok(()))
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.poll_ready.poll_ready(cx)
     }
 
     #[inline]
     fn call(&mut self, req: R) -> Self::Future {
-        self.0.call(req)
+        self.service.call(req)
+    }
+}
+
+/// A source of readiness for services converted from hyper 1.0 to tower-service 0.3.
+///
+/// hyper 1.0's `Service` trait doesn't have a `poll_ready` method so the adapters have to get
+/// readiness from somewhere else. This trait is implemented for [`AlwaysReady`], which is used by
+/// default, and for closures with the signature `FnMut(&mut Context<'_>) -> Poll<Result<(), E>>`.
+///
+/// See [`Hyper1ServiceAsTowerService03Service::with_poll_ready`] and
+/// [`Hyper1HttpServiceAsTowerService03HttpService::with_poll_ready`].
+///
+/// [`Hyper1HttpServiceAsTowerService03HttpService::with_poll_ready`]: crate::Hyper1HttpServiceAsTowerService03HttpService::with_poll_ready
+pub trait PollReady<E> {
+    /// Returns `Poll::Ready(Ok(()))` when the service is able to process requests.
+    ///
+    /// This has the same semantics as tower-service 0.3's `Service::poll_ready`.
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), E>>;
+}
+
+impl<F, E> PollReady<E> for F
+where
+    F: FnMut(&mut Context<'_>) -> Poll<Result<(), E>>,
+{
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), E>> {
+        self(cx)
+    }
+}
+
+/// A [`PollReady`] that is always ready.
+///
+/// This is the default used by [`Hyper1ServiceAsTowerService03Service`] and
+/// [`Hyper1HttpServiceAsTowerService03HttpService`].
+///
+/// [`Hyper1HttpServiceAsTowerService03HttpService`]: crate::Hyper1HttpServiceAsTowerService03HttpService
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AlwaysReady {
+    _priv: (),
+}
+
+impl AlwaysReady {
+    /// Create a new `AlwaysReady`.
+    #[inline]
+    pub fn new() -> Self {
+        Self { _priv: () }
+    }
+}
+
+impl<E> PollReady<E> for AlwaysReady {
+    #[inline]
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), E>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// A [tower-layer 0.3 `Layer`] that produces [`Hyper1ServiceAsTowerService03Service`]s.
+///
+/// [tower-layer 0.3 `Layer`]: https://docs.rs/tower-layer/latest/tower_layer/trait.Layer.html
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Hyper1ServiceAsTowerService03ServiceLayer {
+    _priv: (),
+}
+
+impl Hyper1ServiceAsTowerService03ServiceLayer {
+    /// Create a new `Hyper1ServiceAsTowerService03ServiceLayer`.
+    #[inline]
+    pub fn new() -> Self {
+        Self { _priv: () }
+    }
+}
+
+impl<S> tower_layer_03::Layer<S> for Hyper1ServiceAsTowerService03ServiceLayer {
+    type Service = Hyper1ServiceAsTowerService03Service<S>;
+
+    #[inline]
+    fn layer(&self, inner: S) -> Self::Service {
+        Hyper1ServiceAsTowerService03Service::new(inner)
     }
 }
@@ -29,7 +29,7 @@ async fn tower_service_03_service_to_hyper_1_service() {
     tokio::task::spawn(async move {
         loop {
             let (tcp_stream, _) = tcp_listener.accept().await.unwrap();
-            let tcp_stream = hyper_util::rt::TokioIo::new(tcp_stream);
+            let tcp_stream = TokioIoToHyper1Io::new(tcp_stream);
             tokio::spawn(async move {
                 http1::Builder::new()
                     .serve_connection(tcp_stream, svc)
@@ -58,14 +58,18 @@ async fn tower_service_03_service_to_hyper_1_service() {
 
 #[tokio::test]
 async fn hyper_1_service_to_tower_service_03_service() {
-    async fn handle<B>(req: Request<B>) -> Result<Response<http_body_util::Full<Bytes>>, Infallible>
+    async fn handle<B>(
+        req: hyper_1::Request<B>,
+    ) -> Result<hyper_1::Response<http_body_util::Full<Bytes>>, Infallible>
     where
         B: http_body_1::Body,
     {
         let collected = req.into_body().collect().await.unwrap_or_else(|_| panic!());
         assert_eq!(collected.to_bytes(), "in");
 
-        Ok(Response::new(http_body_util::Full::new(Bytes::from("out"))))
+        Ok(hyper_1::Response::new(http_body_util::Full::new(
+            Bytes::from("out"),
+        )))
     }
 
     let svc = hyper_1::service::service_fn(handle);
@@ -97,3 +101,2409 @@ async fn hyper_1_service_to_tower_service_03_service() {
     let bytes = hyper_014::body::to_bytes(&mut res).await.unwrap();
     assert_eq!(bytes, "out");
 }
+
+#[tokio::test]
+async fn layers_mixing_hyper_1_and_tower_03_middleware() {
+    use std::{future::Future, pin::Pin};
+    use tower::ServiceBuilder;
+
+    // a minimal hyper 1.0 middleware
+    #[derive(Clone)]
+    struct AddHeader<S>(S);
+
+    impl<S, R, B> hyper_1::service::Service<R> for AddHeader<S>
+    where
+        S: hyper_1::service::Service<R, Response = hyper_1::Response<B>>,
+        S::Future: Send + 'static,
+    {
+        type Response = S::Response;
+        type Error = S::Error;
+        type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+        fn call(&self, req: R) -> Self::Future {
+            let future = self.0.call(req);
+            Box::pin(async move {
+                let mut res = future.await?;
+                res.headers_mut()
+                    .insert("x-hyper-1", "true".parse().unwrap());
+                Ok(res)
+            })
+        }
+    }
+
+    async fn handle(
+        req: Request<HttpBody1ToHttpBody04<http_body_util::Full<Bytes>>>,
+    ) -> Result<Response<hyper_014::Body>, Infallible> {
+        let bytes = hyper_014::body::to_bytes(req.into_body())
+            .await
+            .unwrap_or_else(|_| panic!());
+        assert_eq!(bytes, "in");
+        Ok(Response::new(hyper_014::Body::from("out")))
+    }
+
+    let svc = ServiceBuilder::new()
+        .layer_fn(AddHeader)
+        .layer(TowerService03HttpServiceAsHyper1HttpServiceLayer::new())
+        .map_response(|mut res: Response<hyper_014::Body>| {
+            res.headers_mut()
+                .insert("x-tower-03", "true".parse().unwrap());
+            res
+        })
+        .service_fn(handle);
+
+    let res = hyper_1::service::Service::call(
+        &svc,
+        hyper_1::Request::new(http_body_util::Full::new(Bytes::from("in"))),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(res.headers()["x-hyper-1"], "true");
+    assert_eq!(res.headers()["x-tower-03"], "true");
+
+    let collected = res.into_body().collect().await.unwrap();
+    assert_eq!(collected.to_bytes(), "out");
+}
+
+#[tokio::test]
+async fn layers_mixing_tower_03_and_hyper_1_middleware() {
+    use tower::{ServiceBuilder, ServiceExt};
+
+    async fn handle(
+        req: hyper_1::Request<HttpBody04ToHttpBody1<hyper_014::Body>>,
+    ) -> Result<hyper_1::Response<http_body_util::Full<Bytes>>, Infallible> {
+        assert_eq!(req.headers()["x-tower-03"], "true");
+        let collected = req.into_body().collect().await.unwrap();
+        assert_eq!(collected.to_bytes(), "in");
+        Ok(hyper_1::Response::new(http_body_util::Full::new(
+            Bytes::from("out"),
+        )))
+    }
+
+    let svc = ServiceBuilder::new()
+        .map_request(|mut req: Request<hyper_014::Body>| {
+            req.headers_mut()
+                .insert("x-tower-03", "true".parse().unwrap());
+            req
+        })
+        .layer(Hyper1HttpServiceAsTowerService03HttpServiceLayer::new())
+        .service(hyper_1::service::service_fn(handle));
+
+    let res = svc
+        .oneshot(Request::new(hyper_014::Body::from("in")))
+        .await
+        .unwrap();
+
+    let bytes = hyper_014::body::to_bytes(res.into_body()).await.unwrap();
+    assert_eq!(bytes, "out");
+}
+
+#[tokio::test]
+async fn non_http_layers() {
+    use std::future::{ready, Ready};
+    use tower::{ServiceBuilder, ServiceExt};
+
+    // a minimal hyper 1.0 service and middleware
+    #[derive(Clone)]
+    struct Double;
+
+    impl hyper_1::service::Service<u32> for Double {
+        type Response = u32;
+        type Error = Infallible;
+        type Future = Ready<Result<u32, Infallible>>;
+
+        fn call(&self, req: u32) -> Self::Future {
+            ready(Ok(req * 2))
+        }
+    }
+
+    #[derive(Clone)]
+    struct AddOne<S>(S);
+
+    impl<S> hyper_1::service::Service<u32> for AddOne<S>
+    where
+        S: hyper_1::service::Service<u32>,
+    {
+        type Response = S::Response;
+        type Error = S::Error;
+        type Future = S::Future;
+
+        fn call(&self, req: u32) -> Self::Future {
+            self.0.call(req + 1)
+        }
+    }
+
+    // tower-service 0.3 middleware around a hyper 1.0 service
+    let svc = ServiceBuilder::new()
+        .map_response(|res: u32| res + 10)
+        .layer(Hyper1ServiceAsTowerService03ServiceLayer::new())
+        .layer_fn(AddOne)
+        .service(Double);
+    assert_eq!(svc.oneshot(1).await.unwrap(), 14);
+
+    // hyper 1.0 middleware around a tower-service 0.3 service
+    let svc = ServiceBuilder::new()
+        .layer_fn(AddOne)
+        .layer(TowerService03ServiceAsHyper1ServiceLayer::new())
+        .map_request(|req: u32| req * 10)
+        .service_fn(|req: u32| ready(Ok::<_, Infallible>(req * 2)));
+    assert_eq!(hyper_1::service::Service::call(&svc, 1).await.unwrap(), 40);
+}
+
+#[tokio::test]
+async fn extension_traits() {
+    async fn handle(
+        req: Request<HttpBody1ToHttpBody04<http_body_util::Full<Bytes>>>,
+    ) -> Result<Response<HttpBody1ToHttpBody04<http_body_util::Full<Bytes>>>, Infallible> {
+        let bytes = hyper_014::body::to_bytes(req.into_body())
+            .await
+            .unwrap_or_else(|_| panic!());
+        assert_eq!(bytes, "in");
+        Ok(Response::new(
+            http_body_util::Full::new(Bytes::from("out")).into_http_body04(),
+        ))
+    }
+
+    let svc = tower::service_fn(handle).into_hyper1_http_service();
+
+    let req = hyper_1::Request::new(http_body_util::Full::new(Bytes::from("in")));
+    let res = hyper_1::service::Service::call(&svc, req).await.unwrap();
+
+    let collected = res.into_body().collect().await.unwrap();
+    assert_eq!(collected.to_bytes(), "out");
+}
+
+#[tokio::test]
+async fn buffered_service_drives_readiness_on_a_single_instance() {
+    use std::{
+        future::{ready, Ready},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        task::{Context, Poll},
+    };
+    use tokio::sync::Notify;
+
+    type Req = Request<HttpBody1ToHttpBody04<http_body_util::Full<Bytes>>>;
+
+    // keeps its readiness state per instance, like `tower::limit::RateLimit`
+    #[derive(Clone)]
+    struct ReadyOnce {
+        ready: bool,
+        calls: Arc<AtomicUsize>,
+        not_ready: Arc<Notify>,
+    }
+
+    impl ReadyOnce {
+        fn new() -> Self {
+            Self {
+                ready: true,
+                calls: Arc::new(AtomicUsize::new(0)),
+                not_ready: Arc::new(Notify::new()),
+            }
+        }
+    }
+
+    impl tower::Service<Req> for ReadyOnce {
+        type Response = Response<hyper_014::Body>;
+        type Error = Infallible;
+        type Future = Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            if self.ready {
+                Poll::Ready(Ok(()))
+            } else {
+                self.not_ready.notify_one();
+                Poll::Pending
+            }
+        }
+
+        fn call(&mut self, _req: Req) -> Self::Future {
+            self.ready = false;
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            ready(Ok(Response::new(hyper_014::Body::empty())))
+        }
+    }
+
+    fn send_burst<S>(svc: &S) -> Vec<tokio::task::JoinHandle<Result<S::Response, S::Error>>>
+    where
+        S: hyper_1::service::Service<hyper_1::Request<http_body_util::Full<Bytes>>>,
+        S::Future: Send + 'static,
+        S::Response: Send + 'static,
+        S::Error: Send + 'static,
+    {
+        (0..3)
+            .map(|_| {
+                let req = hyper_1::Request::new(http_body_util::Full::new(Bytes::new()));
+                tokio::spawn(svc.call(req))
+            })
+            .collect()
+    }
+
+    // every request drives readiness on its own clone so the whole burst goes through
+    let inner = ReadyOnce::new();
+    let svc = TowerService03HttpServiceAsHyper1HttpService::new(inner.clone());
+    for handle in send_burst(&svc) {
+        handle.await.unwrap().unwrap();
+    }
+    assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+
+    // a single instance is only ready once, the rest of the burst waits for it
+    let inner = ReadyOnce::new();
+    let svc = TowerService03HttpServiceAsHyper1HttpService::buffered(inner.clone(), 16);
+    let handles = send_burst(&svc);
+    inner.not_ready.notified().await;
+    assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    for handle in handles {
+        handle.abort();
+    }
+}
+
+#[tokio::test]
+async fn buffered_concurrency_limit_over_http2() {
+    use hyper_1::{client::conn, server::conn::http2};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[derive(Default)]
+    struct InFlight {
+        current: AtomicUsize,
+        max: AtomicUsize,
+    }
+
+    let in_flight = Arc::new(InFlight::default());
+    let handler_in_flight = in_flight.clone();
+    let handle = move |_req: Request<_>| {
+        let in_flight = handler_in_flight.clone();
+        async move {
+            let current = in_flight.current.fetch_add(1, Ordering::SeqCst) + 1;
+            in_flight.max.fetch_max(current, Ordering::SeqCst);
+            // give other requests the chance to reach the handler if the limit didn't hold
+            for _ in 0..16 {
+                tokio::task::yield_now().await;
+            }
+            in_flight.current.fetch_sub(1, Ordering::SeqCst);
+            Ok::<_, Infallible>(Response::new(hyper_014::Body::empty()))
+        }
+    };
+    let svc = tower::limit::ConcurrencyLimit::new(tower::service_fn(handle), 1);
+    let svc = TowerService03HttpServiceAsHyper1HttpService::buffered(svc, 16);
+
+    let (client, server) = tokio::io::duplex(64 * 1024);
+    tokio::spawn(async move {
+        http2::Builder::new(TokioExecutor::new())
+            .serve_connection(TokioIoToHyper1Io::new(server), svc)
+            .await
+            .unwrap();
+    });
+
+    let (send_request, conn) =
+        conn::http2::handshake(TokioExecutor::new(), TokioIoToHyper1Io::new(client))
+            .await
+            .unwrap();
+    tokio::spawn(conn);
+
+    let handles = (0..8)
+        .map(|_| {
+            let mut send_request = send_request.clone();
+            tokio::spawn(async move {
+                let req = hyper_1::Request::builder()
+                    .uri("http://example.com/")
+                    .body(http_body_util::Empty::<Bytes>::new())
+                    .unwrap();
+                send_request.send_request(req).await.unwrap()
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        assert_eq!(handle.await.unwrap().status(), hyper_1::StatusCode::OK);
+    }
+
+    assert_eq!(in_flight.max.load(Ordering::SeqCst), 1);
+    assert_eq!(in_flight.current.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn hyper_1_service_readiness_is_observed_by_tower_middleware() {
+    use std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        task::{Context, Poll},
+    };
+    use tower::ServiceExt;
+
+    async fn handle<B>(
+        _req: hyper_1::Request<B>,
+    ) -> Result<hyper_1::Response<http_body_util::Empty<Bytes>>, Infallible> {
+        Ok(hyper_1::Response::new(http_body_util::Empty::new()))
+    }
+
+    let ready = Arc::new(AtomicBool::new(false));
+
+    let svc =
+        Hyper1HttpServiceAsTowerService03HttpService::new(hyper_1::service::service_fn(handle))
+            .with_poll_ready({
+                let ready = ready.clone();
+                move |_cx: &mut Context<'_>| {
+                    if ready.load(Ordering::SeqCst) {
+                        Poll::Ready(Ok::<_, Infallible>(()))
+                    } else {
+                        Poll::Pending
+                    }
+                }
+            });
+    let svc = tower::ServiceBuilder::new().load_shed().service(svc);
+
+    let err = svc
+        .clone()
+        .oneshot(Request::new(hyper_014::Body::empty()))
+        .await
+        .unwrap_err();
+    assert!(err.is::<tower::load_shed::error::Overloaded>());
+
+    ready.store(true, Ordering::SeqCst);
+
+    let res = svc
+        .oneshot(Request::new(hyper_014::Body::empty()))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+/// An http-body 1.0 `Body` that yields a fixed sequence of frames.
+struct Frames {
+    frames: std::collections::VecDeque<http_body_1::Frame<Bytes>>,
+    size_hint: Option<u64>,
+}
+
+impl Frames {
+    fn new(frames: impl IntoIterator<Item = http_body_1::Frame<Bytes>>) -> Self {
+        Self {
+            frames: frames.into_iter().collect(),
+            size_hint: None,
+        }
+    }
+
+    /// Report an exact size hint, regardless of the frames.
+    fn with_size_hint(mut self, size_hint: u64) -> Self {
+        self.size_hint = Some(size_hint);
+        self
+    }
+}
+
+impl http_body_1::Body for Frames {
+    type Data = Bytes;
+    type Error = &'static str;
+
+    fn poll_frame(
+        mut self: std::pin::Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Result<http_body_1::Frame<Self::Data>, Self::Error>>> {
+        std::task::Poll::Ready(self.frames.pop_front().map(Ok))
+    }
+
+    fn size_hint(&self) -> http_body_1::SizeHint {
+        self.size_hint
+            .map(http_body_1::SizeHint::with_exact)
+            .unwrap_or_default()
+    }
+}
+
+fn trailers(value: &'static str) -> hyper_1::HeaderMap {
+    let mut trailers = hyper_1::HeaderMap::new();
+    trailers.insert("x-trailer", value.parse().unwrap());
+    trailers
+}
+
+#[tokio::test]
+async fn unexpected_frames_are_dropped_by_default() {
+    use http_body_04::Body as _;
+
+    let mut body = HttpBody1ToHttpBody04::new(Frames::new([
+        http_body_1::Frame::data(Bytes::from("a")),
+        http_body_1::Frame::trailers(trailers("first")),
+        http_body_1::Frame::data(Bytes::from("b")),
+        http_body_1::Frame::trailers(trailers("second")),
+    ]));
+
+    assert_eq!(body.data().await.unwrap().unwrap(), "a");
+    assert!(body.data().await.is_none());
+    let trailers = body.trailers().await.unwrap().unwrap();
+    assert_eq!(trailers["x-trailer"], "first");
+    assert!(body.is_end_stream());
+}
+
+#[tokio::test]
+async fn unexpected_frames_are_passed_to_the_policy() {
+    use http_body_04::Body as _;
+
+    let mut seen = Vec::new();
+    let mut body = HttpBody1ToHttpBody04::new(Frames::new([
+        http_body_1::Frame::data(Bytes::from("a")),
+        http_body_1::Frame::trailers(trailers("first")),
+        http_body_1::Frame::trailers(trailers("second")),
+        http_body_1::Frame::data(Bytes::from("b")),
+    ]))
+    .on_unexpected_frame(|frame| match frame {
+        UnexpectedFrame::TrailersAfterTrailers(trailers) => {
+            seen.push(trailers);
+            Ok(())
+        }
+        UnexpectedFrame::DataAfterTrailers(_) => Err("data after trailers"),
+        UnexpectedFrame::Unknown(_) => Ok(()),
+    });
+
+    assert_eq!(body.data().await.unwrap().unwrap(), "a");
+    assert!(body.data().await.is_none());
+    assert_eq!(body.trailers().await.unwrap_err(), "data after trailers");
+
+    drop(body);
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0]["x-trailer"], "second");
+}
+
+#[tokio::test]
+async fn strict_mode_reports_protocol_violations() {
+    use http_body_04::Body as _;
+
+    let mut body = HttpBody1ToHttpBody04::new(Frames::new([
+        http_body_1::Frame::data(Bytes::from("a")),
+        http_body_1::Frame::trailers(trailers("first")),
+        http_body_1::Frame::trailers(trailers("second")),
+    ]))
+    .strict();
+
+    assert_eq!(body.data().await.unwrap().unwrap(), "a");
+    assert!(body.data().await.is_none());
+    let err = body.trailers().await.unwrap_err();
+    assert!(matches!(err, Error::MultipleTrailers));
+    assert!(!err.is_body());
+
+    let body = HttpBody04ToHttpBody1::new(hyper_014::Body::from("a")).strict();
+    let collected: Result<_, Error> = body.collect().await;
+    assert_eq!(collected.unwrap().to_bytes(), "a");
+}
+
+#[tokio::test]
+async fn strict_mode_chains_body_errors() {
+    use std::error::Error as _;
+
+    let (tx, body) = hyper_014::Body::channel();
+    tx.abort();
+
+    let err = HttpBody04ToHttpBody1::new(body)
+        .strict()
+        .collect()
+        .await
+        .unwrap_err();
+    assert!(err.is_body());
+    assert!(err.source().unwrap().is::<hyper_014::Error>());
+}
+
+#[tokio::test]
+async fn hyper_1_incoming_into_hyper_014_body() {
+    use http_body_04::Body as _;
+
+    async fn handle(
+        req: hyper_1::Request<hyper_1::body::Incoming>,
+    ) -> Result<hyper_1::Response<http_body_util::Empty<Bytes>>, Infallible> {
+        let empty = req.uri().path() == "/empty";
+        let body = HttpBody1ToHttpBody04::new(req.into_body()).into_hyper014_body();
+        assert_eq!(body.is_end_stream(), empty);
+        if empty {
+            assert_eq!(body.size_hint().exact(), Some(0));
+        }
+
+        let bytes = hyper_014::body::to_bytes(body).await.unwrap();
+        assert_eq!(bytes, if empty { "" } else { "hello" });
+
+        Ok(hyper_1::Response::new(http_body_util::Empty::new()))
+    }
+
+    let (client, server) = tokio::io::duplex(1024);
+    tokio::spawn(async move {
+        http1::Builder::new()
+            .serve_connection(
+                TokioIoToHyper1Io::new(server),
+                hyper_1::service::service_fn(handle),
+            )
+            .await
+            .unwrap();
+    });
+
+    let (mut send_request, conn) =
+        hyper_1::client::conn::http1::handshake(TokioIoToHyper1Io::new(client))
+            .await
+            .unwrap();
+    tokio::spawn(conn);
+
+    for (path, body) in [("/empty", ""), ("/data", "hello")] {
+        let req = hyper_1::Request::builder()
+            .uri(path)
+            .body(http_body_util::Full::new(Bytes::from(body)))
+            .unwrap();
+        let res = send_request.send_request(req).await.unwrap();
+        assert_eq!(res.status(), hyper_1::StatusCode::OK);
+    }
+}
+
+#[tokio::test]
+async fn hyper_1_incoming_as_hyper_014_body() {
+    use http_body_04::Body as _;
+    use hyper_1::server::conn::http2;
+
+    async fn handle(
+        req: hyper_1::Request<hyper_1::body::Incoming>,
+    ) -> Result<hyper_1::Response<Hyper014BodyAsHyper1Body>, Infallible> {
+        let mut body = Hyper1IncomingAsHyper014Body::new(req.into_body());
+        assert_eq!(body.size_hint().exact(), Some(5));
+
+        let mut data = Vec::new();
+        while let Some(chunk) = body.data().await {
+            data.extend_from_slice(&chunk.unwrap());
+        }
+        assert_eq!(data, b"hello");
+        assert!(!body.is_end_stream());
+
+        let trailers = body.trailers().await.unwrap().unwrap();
+        assert_eq!(trailers["x-trailer"], "value");
+        assert!(body.is_end_stream());
+
+        Ok(hyper_1::Response::new(Hyper014BodyAsHyper1Body::new(
+            hyper_014::Body::from("out"),
+        )))
+    }
+
+    let tcp_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = tcp_listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (tcp_stream, _) = tcp_listener.accept().await.unwrap();
+        http2::Builder::new(TokioExecutor::new())
+            .serve_connection(
+                TokioIoToHyper1Io::new(tcp_stream),
+                hyper_1::service::service_fn(handle),
+            )
+            .await
+            .unwrap();
+    });
+
+    let (mut tx, body) = hyper_014::Body::channel();
+    tokio::spawn(async move {
+        tx.send_data(Bytes::from("hello")).await.unwrap();
+        let mut trailers = http::HeaderMap::new();
+        trailers.insert("x-trailer", "value".parse().unwrap());
+        tx.send_trailers(trailers).await.unwrap();
+    });
+
+    let client = hyper_014::Client::builder()
+        .http2_only(true)
+        .build_http::<hyper_014::Body>();
+    let req = Request::builder()
+        .uri(format!("http://{addr}"))
+        .header(http::header::CONTENT_LENGTH, 5)
+        .body(body)
+        .unwrap();
+    let res = client.request(req).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let bytes = hyper_014::body::to_bytes(res).await.unwrap();
+    assert_eq!(bytes, "out");
+}
+
+#[tokio::test]
+async fn hyper_014_body_as_hyper_1_body() {
+    use http_body_1::Body as _;
+
+    let body = Hyper014BodyAsHyper1Body::new(hyper_014::Body::from("abc"));
+    assert_eq!(body.size_hint().exact(), Some(3));
+    assert!(!body.is_end_stream());
+    let body = Hyper014BodyAsHyper1Body::new(hyper_014::Body::empty());
+    assert!(body.is_end_stream());
+
+    let (mut tx, body) = hyper_014::Body::channel();
+    tokio::spawn(async move {
+        tx.send_data(Bytes::from("a")).await.unwrap();
+        let mut trailers = http::HeaderMap::new();
+        trailers.insert("x-trailer", "value".parse().unwrap());
+        tx.send_trailers(trailers).await.unwrap();
+    });
+
+    let collected = Hyper014BodyAsHyper1Body::new(body).collect().await.unwrap();
+    assert_eq!(collected.trailers().unwrap()["x-trailer"], "value");
+    assert_eq!(collected.to_bytes(), "a");
+}
+
+#[tokio::test]
+async fn boxed_bodies() {
+    let body: BoxBody04To1 = hyper_014::Body::from("a").into_http_body1_boxed();
+    assert_eq!(body.collect().await.unwrap().to_bytes(), "a");
+
+    let (tx, body) = hyper_014::Body::channel();
+    tx.abort();
+    let body: UnsyncBoxBody04To1 = body.into_http_body1_boxed_unsync();
+    let err = body.collect().await.unwrap_err();
+    assert!(err.is::<hyper_014::Error>());
+
+    let body: BoxBody1To04 = http_body_util::Full::new(Bytes::from("b")).into_http_body04_boxed();
+    assert_eq!(hyper_014::body::to_bytes(body).await.unwrap(), "b");
+
+    let body: UnsyncBoxBody1To04 = Frames::new([
+        http_body_1::Frame::data(Bytes::from("c")),
+        http_body_1::Frame::trailers(trailers("value")),
+    ])
+    .into_http_body04_boxed_unsync();
+    let mut body = std::pin::pin!(body);
+    assert_eq!(
+        http_body_04::Body::data(&mut body).await.unwrap().unwrap(),
+        "c"
+    );
+    let trailers = http_body_04::Body::trailers(&mut body)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(trailers["x-trailer"], "value");
+}
+
+#[tokio::test]
+async fn box_response_body() {
+    use axum::{routing::get, Router};
+    use tower::ServiceExt;
+
+    type ReqBody = HttpBody1ToHttpBody04<http_body_util::Empty<Bytes>>;
+
+    struct App {
+        service: TowerService03HttpServiceAsHyper1HttpService<
+            BoxResponseBody<Router<(), ReqBody>>,
+            ReqBody,
+        >,
+    }
+
+    let app = App {
+        service: TowerService03HttpServiceAsHyper1HttpService::new(
+            Router::new().route("/", get(|| async { "out" })),
+        )
+        .box_response_body(),
+    };
+
+    let req = hyper_1::Request::new(http_body_util::Empty::new());
+    let res: hyper_1::Response<UnsyncBoxBody04To1> =
+        hyper_1::service::Service::call(&app.service, req)
+            .await
+            .unwrap();
+    assert_eq!(res.into_body().collect().await.unwrap().to_bytes(), "out");
+
+    let svc = tower::ServiceBuilder::new()
+        .layer(BoxResponseBodyLayer::new())
+        .service(Router::<(), hyper_014::Body>::new().route("/", get(|| async { "out" })));
+    let res = svc
+        .oneshot(Request::new(hyper_014::Body::empty()))
+        .await
+        .unwrap();
+    let bytes = hyper_014::body::to_bytes(res.into_body()).await.unwrap();
+    assert_eq!(bytes, "out");
+}
+
+#[tokio::test]
+async fn handle_error_keeps_connection_open() {
+    async fn handle<B>(_req: Request<B>) -> Result<Response<hyper_014::Body>, std::io::Error> {
+        Err(std::io::Error::other("oops"))
+    }
+
+    let svc = TowerService03HttpServiceAsHyper1HttpService::new(tower::service_fn(handle))
+        .handle_error(|err: std::io::Error| {
+            let mut res = Response::new(hyper_014::Body::from(err.to_string()));
+            *res.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+            res
+        });
+
+    let (client, server) = tokio::io::duplex(1024);
+    tokio::spawn(async move {
+        http1::Builder::new()
+            .serve_connection(TokioIoToHyper1Io::new(server), svc)
+            .await
+            .unwrap();
+    });
+
+    let (mut send_request, conn) =
+        hyper_1::client::conn::http1::handshake(TokioIoToHyper1Io::new(client))
+            .await
+            .unwrap();
+    tokio::spawn(conn);
+
+    for _ in 0..2 {
+        send_request.ready().await.unwrap();
+        let res = send_request
+            .send_request(hyper_1::Request::new(http_body_util::Empty::<Bytes>::new()))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), hyper_1::StatusCode::INTERNAL_SERVER_ERROR);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(bytes, "oops");
+    }
+}
+
+#[tokio::test]
+async fn box_error() {
+    use tower::ServiceExt;
+
+    async fn handle<B>(_req: Request<B>) -> Result<Response<hyper_014::Body>, std::io::Error> {
+        Err(std::io::Error::other("oops"))
+    }
+
+    let svc =
+        TowerService03HttpServiceAsHyper1HttpService::new(tower::service_fn(handle)).box_error();
+
+    let req = hyper_1::Request::new(http_body_util::Empty::<Bytes>::new());
+    let err: tower::BoxError = hyper_1::service::Service::call(&svc, req)
+        .await
+        .unwrap_err();
+    assert!(err.is::<std::io::Error>());
+
+    let svc = tower::ServiceBuilder::new()
+        .layer(MapErrToBoxErrorLayer::new())
+        .service(tower::service_fn(handle::<hyper_014::Body>));
+    let err = svc
+        .oneshot(Request::new(hyper_014::Body::empty()))
+        .await
+        .unwrap_err();
+    assert!(err.is::<std::io::Error>());
+}
+
+#[tokio::test]
+async fn body_adapters_enforce_limit() {
+    let body = HttpBody04ToHttpBody1::new(hyper_014::Body::from("abcd")).limit(3);
+    let err = body.collect().await.unwrap_err();
+    assert!(matches!(err, Error::LengthLimitExceeded));
+
+    let body = HttpBody04ToHttpBody1::new(hyper_014::Body::from("abc")).limit(3);
+    assert_eq!(body.collect().await.unwrap().to_bytes(), "abc");
+
+    let body = HttpBody1ToHttpBody04::new(Frames::new([
+        http_body_1::Frame::data(Bytes::from("ab")),
+        http_body_1::Frame::data(Bytes::from("cd")),
+    ]))
+    .limit(3);
+    let err = hyper_014::body::to_bytes(body).await.unwrap_err();
+    assert!(matches!(err, Error::LengthLimitExceeded));
+}
+
+#[tokio::test]
+async fn request_body_limit() {
+    async fn handle<B>(req: Request<B>) -> Result<Response<hyper_014::Body>, Infallible>
+    where
+        B: http_body_04::Body<Error = Error>,
+    {
+        let res = match hyper_014::body::to_bytes(req.into_body()).await {
+            Ok(bytes) => Response::new(hyper_014::Body::from(bytes)),
+            Err(err) => {
+                assert!(matches!(err, Error::LengthLimitExceeded));
+                let mut res = Response::new(hyper_014::Body::empty());
+                *res.status_mut() = StatusCode::BAD_REQUEST;
+                res
+            }
+        };
+        Ok(res)
+    }
+
+    let svc = TowerService03HttpServiceAsHyper1HttpService::new(tower::service_fn(handle))
+        .request_body_limit(3);
+
+    let (client, server) = tokio::io::duplex(1024);
+    tokio::spawn(async move {
+        http1::Builder::new()
+            .serve_connection(TokioIoToHyper1Io::new(server), svc)
+            .await
+            .unwrap();
+    });
+
+    let (mut send_request, conn) =
+        hyper_1::client::conn::http1::handshake(TokioIoToHyper1Io::new(client))
+            .await
+            .unwrap();
+    tokio::spawn(conn);
+
+    // within the limit
+    send_request.ready().await.unwrap();
+    let res = send_request
+        .send_request(
+            hyper_1::Request::builder()
+                .method(hyper_1::Method::POST)
+                .body(Frames::new([http_body_1::Frame::data(Bytes::from("abc"))]))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.status(), hyper_1::StatusCode::OK);
+    let bytes = res.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(bytes, "abc");
+
+    // streaming body exceeding the limit
+    send_request.ready().await.unwrap();
+    let res = send_request
+        .send_request(
+            hyper_1::Request::builder()
+                .method(hyper_1::Method::POST)
+                .body(Frames::new([
+                    http_body_1::Frame::data(Bytes::from("ab")),
+                    http_body_1::Frame::data(Bytes::from("cd")),
+                ]))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    // the service's response is replaced since the limit was exceeded while reading the body
+    assert_eq!(res.status(), hyper_1::StatusCode::PAYLOAD_TOO_LARGE);
+    res.into_body().collect().await.unwrap();
+
+    // `content-length` exceeding the limit
+    send_request.ready().await.unwrap();
+    let req = hyper_1::Request::builder()
+        .method(hyper_1::Method::POST)
+        .header(hyper_1::header::CONTENT_LENGTH, 4)
+        .body(Frames::new([http_body_1::Frame::data(Bytes::from("abcd"))]))
+        .unwrap();
+    let res = send_request.send_request(req).await.unwrap();
+    assert_eq!(res.status(), hyper_1::StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn check_size_hint() {
+    fn data(frames: &[&'static str]) -> Vec<http_body_1::Frame<Bytes>> {
+        frames
+            .iter()
+            .map(|data| http_body_1::Frame::data(Bytes::from(*data)))
+            .collect()
+    }
+
+    // too little data
+    let body = HttpBody1ToHttpBody04::new(Frames::new(data(&["ab"])).with_size_hint(3))
+        .strict()
+        .check_size_hint();
+    let err = hyper_014::body::to_bytes(body).await.unwrap_err();
+    assert!(matches!(
+        err,
+        Error::SizeHintMismatch {
+            expected: 3,
+            actual: 2
+        }
+    ));
+
+    // too much data
+    let body = HttpBody1ToHttpBody04::new(Frames::new(data(&["ab", "cd"])).with_size_hint(3))
+        .strict()
+        .check_size_hint();
+    let err = hyper_014::body::to_bytes(body).await.unwrap_err();
+    assert!(matches!(
+        err,
+        Error::SizeHintMismatch {
+            expected: 3,
+            actual: 4
+        }
+    ));
+
+    // matching data
+    let body = HttpBody1ToHttpBody04::new(Frames::new(data(&["ab", "c"])).with_size_hint(3))
+        .strict()
+        .check_size_hint();
+    assert_eq!(hyper_014::body::to_bytes(body).await.unwrap(), "abc");
+
+    // http-body 0.4 to http-body 1.0
+    let inner = HttpBody1ToHttpBody04::new(Frames::new(data(&["ab"])).with_size_hint(3));
+    let err = HttpBody04ToHttpBody1::new(inner)
+        .strict()
+        .check_size_hint()
+        .collect()
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::SizeHintMismatch {
+            expected: 3,
+            actual: 2
+        }
+    ));
+
+    let body = HttpBody04ToHttpBody1::new(hyper_014::Body::from("abc"))
+        .strict()
+        .check_size_hint();
+    assert_eq!(body.collect().await.unwrap().to_bytes(), "abc");
+}
+
+#[tokio::test]
+async fn stream_body() {
+    fn body() -> StreamBody<impl futures_util::Stream<Item = Result<Bytes, Infallible>>> {
+        StreamBody::new(futures_util::stream::iter(vec![
+            Ok(Bytes::from("foo")),
+            Ok(Bytes::from("bar")),
+        ]))
+    }
+
+    let bytes = hyper_014::body::to_bytes(body()).await.unwrap();
+    assert_eq!(bytes, "foobar");
+
+    let collected = body().collect().await.unwrap();
+    assert!(collected.trailers().is_none());
+    assert_eq!(collected.to_bytes(), "foobar");
+}
+
+#[tokio::test]
+async fn frame_stream_body() {
+    fn body() -> FrameStreamBody<
+        impl futures_util::Stream<Item = Result<http_body_1::Frame<Bytes>, Infallible>>,
+    > {
+        let mut trailers = hyper_1::HeaderMap::new();
+        trailers.insert("foo", "bar".parse().unwrap());
+        FrameStreamBody::new(futures_util::stream::iter(vec![
+            Ok(http_body_1::Frame::data(Bytes::from("foo"))),
+            Ok(http_body_1::Frame::data(Bytes::from("bar"))),
+            Ok(http_body_1::Frame::trailers(trailers)),
+        ]))
+    }
+
+    // http-body 0.4
+    let mut frames = std::pin::pin!(body());
+    let mut data = Vec::new();
+    while let Some(chunk) = http_body_04::Body::data(&mut frames).await {
+        data.extend_from_slice(&chunk.unwrap());
+    }
+    assert_eq!(data, b"foobar");
+    let trailers = http_body_04::Body::trailers(&mut frames)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(trailers["foo"], "bar");
+    assert!(http_body_04::Body::is_end_stream(&*frames));
+
+    // http-body 1.0
+    let collected = body().collect().await.unwrap();
+    assert_eq!(collected.trailers().unwrap()["foo"], "bar");
+    assert_eq!(collected.to_bytes(), "foobar");
+
+    // trailers without reading the data first
+    let mut frames = std::pin::pin!(body());
+    let trailers = http_body_04::Body::trailers(&mut frames)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(trailers["foo"], "bar");
+}
+
+#[tokio::test]
+async fn with_trailers_and_take_trailers() {
+    // http-body 0.4 to http-body 1.0
+    let mut inner_trailers = http::HeaderMap::new();
+    inner_trailers.insert("x-inner", "inner".parse().unwrap());
+    inner_trailers.insert("x-trailer", "inner".parse().unwrap());
+    let inner = TrailersBody {
+        chunks: 1,
+        trailers: Some(Ok(inner_trailers)),
+    };
+    let collected = HttpBody04ToHttpBody1::new(inner)
+        .with_trailers(trailers("attached"))
+        .collect()
+        .await
+        .unwrap();
+    let collected_trailers = collected.trailers().unwrap();
+    assert_eq!(collected_trailers["x-inner"], "inner");
+    assert_eq!(collected_trailers["x-trailer"], "attached");
+
+    let inner = TrailersBody {
+        chunks: 1,
+        trailers: None,
+    };
+    let mut body =
+        std::pin::pin!(HttpBody04ToHttpBody1::new(inner).with_trailers(trailers("attached")));
+    assert!(body.frame().await.unwrap().unwrap().is_data());
+    let frame = body.frame().await.unwrap().unwrap();
+    assert_eq!(frame.into_trailers().unwrap()["x-trailer"], "attached");
+    assert!(body.frame().await.is_none());
+    assert!(http_body_1::Body::is_end_stream(&*body));
+
+    // http-body 1.0 to http-body 0.4
+    let inner = Frames::new([
+        http_body_1::Frame::data(Bytes::from("a")),
+        http_body_1::Frame::trailers(trailers("inner")),
+        http_body_1::Frame::data(Bytes::from("b")),
+    ]);
+    let mut body = std::pin::pin!(HttpBody1ToHttpBody04::new(inner));
+    assert!(body.as_mut().take_trailers().is_none());
+    assert_eq!(
+        http_body_04::Body::data(&mut body).await.unwrap().unwrap(),
+        "a"
+    );
+    assert!(http_body_04::Body::data(&mut body).await.is_none());
+    assert_eq!(body.as_mut().take_trailers().unwrap()["x-trailer"], "inner");
+    // the data after the trailers isn't yielded once the trailers have been taken
+    assert!(http_body_04::Body::data(&mut body).await.is_none());
+    assert!(http_body_04::Body::trailers(&mut body)
+        .await
+        .unwrap()
+        .is_none());
+
+    let inner = Frames::new([http_body_1::Frame::data(Bytes::from("a"))]);
+    let body = HttpBody1ToHttpBody04::new(inner).with_trailers(trailers("attached"));
+    let mut body = std::pin::pin!(body);
+    assert!(!http_body_04::Body::is_end_stream(&*body));
+    assert_eq!(
+        http_body_04::Body::data(&mut body).await.unwrap().unwrap(),
+        "a"
+    );
+    assert!(http_body_04::Body::data(&mut body).await.is_none());
+    let body_trailers = http_body_04::Body::trailers(&mut body)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(body_trailers["x-trailer"], "attached");
+    assert!(http_body_04::Body::is_end_stream(&*body));
+}
+
+/// An http-body 0.4 `Body` that yields `chunks` pieces of data followed by `trailers`.
+struct TrailersBody {
+    chunks: usize,
+    trailers: Option<Result<http::HeaderMap, std::io::Error>>,
+}
+
+impl http_body_04::Body for TrailersBody {
+    type Data = Bytes;
+    type Error = std::io::Error;
+
+    fn poll_data(
+        mut self: std::pin::Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Result<Self::Data, Self::Error>>> {
+        if self.chunks == 0 {
+            return std::task::Poll::Ready(None);
+        }
+        self.chunks -= 1;
+        std::task::Poll::Ready(Some(Ok(Bytes::from("chunk"))))
+    }
+
+    fn poll_trailers(
+        mut self: std::pin::Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        std::task::Poll::Ready(self.trailers.take().transpose())
+    }
+}
+
+/// The cases of the trailer tests, selected by the request path.
+#[derive(Clone, Copy, Debug)]
+enum TrailersCase {
+    Empty,
+    ManyChunks,
+    Error,
+}
+
+impl TrailersCase {
+    const ALL: [Self; 3] = [Self::Empty, Self::ManyChunks, Self::Error];
+
+    fn path(self) -> &'static str {
+        match self {
+            Self::Empty => "/empty",
+            Self::ManyChunks => "/many-chunks",
+            Self::Error => "/error",
+        }
+    }
+
+    fn from_path(path: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|case| case.path() == path)
+            .unwrap()
+    }
+
+    fn chunks(self) -> usize {
+        match self {
+            Self::Empty => 0,
+            Self::ManyChunks => 100,
+            Self::Error => 2,
+        }
+    }
+
+    fn response_body(self, value: &'static str) -> TrailersBody {
+        let trailers = match self {
+            Self::Empty | Self::ManyChunks => Ok(trailers_02(value)),
+            Self::Error => Err(std::io::Error::other("trailers failed")),
+        };
+        TrailersBody {
+            chunks: self.chunks(),
+            trailers: Some(trailers),
+        }
+    }
+}
+
+#[tokio::test]
+async fn trailers_through_tower_service_03_service_to_hyper_1_service_over_http2() {
+    use hyper_1::{client::conn, server::conn::http2};
+
+    async fn handle<B>(req: Request<B>) -> Result<Response<TrailersBody>, Infallible>
+    where
+        B: http_body_04::Body,
+        B::Error: std::fmt::Debug,
+    {
+        let case = TrailersCase::from_path(req.uri().path());
+        let (len, trailers) = read_body(req.into_body()).await.unwrap();
+        assert_eq!(len, case.chunks() * "chunk".len());
+        assert_eq!(trailers.unwrap()["x-trailer"], "request");
+
+        Ok(Response::new(case.response_body("response")))
+    }
+
+    let tcp_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = tcp_listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (tcp_stream, _) = tcp_listener.accept().await.unwrap();
+        let svc = TowerService03HttpServiceAsHyper1HttpService::new(tower::service_fn(handle));
+        http2::Builder::new(TokioExecutor::new())
+            .serve_connection(TokioIoToHyper1Io::new(tcp_stream), svc)
+            .await
+            .unwrap();
+    });
+
+    let tcp_stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let (mut send_request, conn) =
+        conn::http2::handshake(TokioExecutor::new(), TokioIoToHyper1Io::new(tcp_stream))
+            .await
+            .unwrap();
+    tokio::spawn(conn);
+
+    for case in TrailersCase::ALL {
+        // the request body always has trailers, only the response body fails
+        let req = hyper_1::Request::builder()
+            .method(hyper_1::Method::POST)
+            .uri(format!("http://{addr}{}", case.path()))
+            .body(HttpBody04ToHttpBody1::new(TrailersBody {
+                chunks: case.chunks(),
+                trailers: Some(Ok(trailers_02("request"))),
+            }))
+            .unwrap();
+        // the error can reset the stream before the response head is received
+        let result = match send_request.send_request(req).await {
+            Ok(res) => res.into_body().collect().await,
+            Err(err) => Err(err),
+        };
+
+        match case {
+            TrailersCase::Empty | TrailersCase::ManyChunks => {
+                let collected = result.unwrap();
+                assert_eq!(collected.trailers().unwrap()["x-trailer"], "response");
+                assert_eq!(
+                    collected.to_bytes().len(),
+                    case.chunks() * "chunk".len(),
+                    "{case:?}"
+                );
+            }
+            TrailersCase::Error => {
+                result.unwrap_err();
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn trailers_through_hyper_1_service_to_tower_service_03_service_over_http2() {
+    async fn handle<B>(
+        req: hyper_1::Request<B>,
+    ) -> Result<hyper_1::Response<HttpBody04ToHttpBody1<TrailersBody>>, Infallible>
+    where
+        B: http_body_1::Body,
+        B::Error: std::fmt::Debug,
+    {
+        let case = TrailersCase::from_path(req.uri().path());
+        let collected = req.into_body().collect().await.unwrap();
+        assert_eq!(collected.trailers().unwrap()["x-trailer"], "request");
+        assert_eq!(collected.to_bytes().len(), case.chunks() * "chunk".len());
+
+        Ok(hyper_1::Response::new(HttpBody04ToHttpBody1::new(
+            case.response_body("response"),
+        )))
+    }
+
+    let svc =
+        Hyper1HttpServiceAsTowerService03HttpService::new(hyper_1::service::service_fn(handle));
+
+    let tcp_listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = tcp_listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        hyper_014::Server::from_tcp(tcp_listener)
+            .unwrap()
+            .http2_only(true)
+            .serve(tower::make::Shared::new(svc))
+            .await
+            .unwrap();
+    });
+
+    let client = hyper_014::Client::builder()
+        .http2_only(true)
+        .build_http::<TrailersBody>();
+
+    for case in TrailersCase::ALL {
+        let req = Request::builder()
+            .method(http::Method::POST)
+            .uri(format!("http://{addr}{}", case.path()))
+            .body(TrailersBody {
+                chunks: case.chunks(),
+                trailers: Some(Ok(trailers_02("request"))),
+            })
+            .unwrap();
+        // the error can reset the stream before the response head is received
+        let result = match client.request(req).await {
+            Ok(res) => {
+                assert_eq!(res.version(), http::Version::HTTP_2);
+                read_body(res.into_body()).await
+            }
+            Err(err) => Err(err),
+        };
+
+        match case {
+            TrailersCase::Empty | TrailersCase::ManyChunks => {
+                let (len, trailers) = result.unwrap();
+                assert_eq!(len, case.chunks() * "chunk".len(), "{case:?}");
+                assert_eq!(trailers.unwrap()["x-trailer"], "response");
+            }
+            TrailersCase::Error => {
+                result.unwrap_err();
+            }
+        }
+    }
+}
+
+/// Read an http-body 0.4 `Body` to the end, returning the length of its data and its trailers.
+async fn read_body<B>(body: B) -> Result<(usize, Option<http::HeaderMap>), B::Error>
+where
+    B: http_body_04::Body,
+{
+    let mut body = std::pin::pin!(body);
+    let mut len = 0;
+    while let Some(chunk) = http_body_04::Body::data(&mut body).await {
+        len += bytes::Buf::remaining(&chunk?);
+    }
+    let trailers = http_body_04::Body::trailers(&mut body).await?;
+    Ok((len, trailers))
+}
+
+fn trailers_02(value: &'static str) -> http::HeaderMap {
+    let mut trailers = http::HeaderMap::new();
+    trailers.insert("x-trailer", value.parse().unwrap());
+    trailers
+}
+
+/// A tonic `Codec` for UTF-8 string messages, so the gRPC tests don't need protobuf.
+#[derive(Clone, Copy, Debug, Default)]
+struct StringCodec;
+
+impl tonic_010::codec::Codec for StringCodec {
+    type Encode = String;
+    type Decode = String;
+    type Encoder = Self;
+    type Decoder = Self;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        Self
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        Self
+    }
+}
+
+impl tonic_010::codec::Encoder for StringCodec {
+    type Item = String;
+    type Error = tonic_010::Status;
+
+    fn encode(
+        &mut self,
+        item: Self::Item,
+        dst: &mut tonic_010::codec::EncodeBuf<'_>,
+    ) -> Result<(), Self::Error> {
+        bytes::BufMut::put_slice(dst, item.as_bytes());
+        Ok(())
+    }
+}
+
+impl tonic_010::codec::Decoder for StringCodec {
+    type Item = String;
+    type Error = tonic_010::Status;
+
+    fn decode(
+        &mut self,
+        src: &mut tonic_010::codec::DecodeBuf<'_>,
+    ) -> Result<Option<Self::Item>, Self::Error> {
+        let bytes = bytes::Buf::copy_to_bytes(src, bytes::Buf::remaining(src));
+        String::from_utf8(bytes.to_vec())
+            .map(Some)
+            .map_err(|err| tonic_010::Status::internal(err.to_string()))
+    }
+}
+
+/// A gRPC service that echoes the request message and fails for empty messages.
+#[derive(Clone, Copy, Debug)]
+struct EchoServer;
+
+impl tonic_010::server::NamedService for EchoServer {
+    const NAME: &'static str = "test.Echo";
+}
+
+impl<B> tower_service_03::Service<Request<B>> for EchoServer
+where
+    B: http_body_04::Body + Send + 'static,
+    B::Error: Into<tower::BoxError> + Send,
+{
+    type Response = Response<tonic_010::body::BoxBody>;
+    type Error = Infallible;
+    type Future = std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>,
+    >;
+
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let echo = tower::service_fn(|req: tonic_010::Request<String>| async move {
+            let message = req.into_inner();
+            if message.is_empty() {
+                return Err(tonic_010::Status::invalid_argument("empty message"));
+            }
+            Ok(tonic_010::Response::new(message))
+        });
+        Box::pin(async move {
+            let mut grpc = tonic_010::server::Grpc::new(StringCodec);
+            Ok(grpc.unary(echo, req).await)
+        })
+    }
+}
+
+#[tokio::test]
+async fn tonic_over_hyper_1_http2() {
+    use hyper_1::{client::conn, server::conn::http2};
+    use tonic_010::{transport::server::Routes, Code};
+
+    let routes = Routes::new(EchoServer);
+    let svc =
+        TowerService03HttpServiceAsHyper1HttpService::new(tonic::IntoHyper014Body::new(routes));
+
+    let tcp_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = tcp_listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (tcp_stream, _) = tcp_listener.accept().await.unwrap();
+        http2::Builder::new(TokioExecutor::new())
+            .serve_connection(TokioIoToHyper1Io::new(tcp_stream), svc)
+            .await
+            .unwrap();
+    });
+
+    let tcp_stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let (send_request, conn) =
+        conn::http2::handshake(TokioExecutor::new(), TokioIoToHyper1Io::new(tcp_stream))
+            .await
+            .unwrap();
+    tokio::spawn(conn);
+    let channel = Hyper1SendRequestAsTowerService03HttpService::new(send_request);
+    let client =
+        tonic_010::client::Grpc::with_origin(channel, format!("http://{addr}").parse().unwrap());
+
+    let call = |message: &str, path: &'static str| {
+        let req = tonic_010::Request::new(message.to_owned());
+        let path = http::uri::PathAndQuery::from_static(path);
+        let mut client = client.clone();
+        async move {
+            client.ready().await.unwrap();
+            client.unary(req, path, StringCodec).await
+        }
+    };
+
+    // the response message
+    let res = call("hello", "/test.Echo/Echo").await.unwrap();
+    assert_eq!(res.into_inner(), "hello");
+
+    // `grpc-status` in the trailers
+    let status = call("", "/test.Echo/Echo").await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert_eq!(status.message(), "empty message");
+
+    // `grpc-status` in the headers of a response without a body
+    let status = call("hello", "/test.Missing/Echo").await.unwrap_err();
+    assert_eq!(status.code(), Code::Unimplemented);
+}
+
+#[tokio::test]
+async fn connection_info_is_added_to_requests() {
+    use axum::{
+        extract::{ConnectInfo, Extension},
+        routing::get,
+        Router,
+    };
+    use hyper_1::client::conn;
+    use std::net::SocketAddr;
+
+    async fn handler(
+        ConnectInfo(remote_addr): ConnectInfo<SocketAddr>,
+        Extension(info): Extension<ConnectionInfo>,
+    ) -> String {
+        assert_eq!(remote_addr, info.remote_addr());
+        format!("{} {}", info.remote_addr(), info.local_addr())
+    }
+
+    let app = Router::new().route("/", get(handler));
+
+    let tcp_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = tcp_listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        loop {
+            let (tcp_stream, remote_addr) = tcp_listener.accept().await.unwrap();
+            let info = ConnectionInfo::new(remote_addr, tcp_stream.local_addr().unwrap());
+            let svc = TowerService03HttpServiceAsHyper1HttpService::new(app.clone())
+                .connection_info(info)
+                .connection_info(ConnectInfo(remote_addr));
+            tokio::spawn(async move {
+                http1::Builder::new()
+                    .serve_connection(TokioIoToHyper1Io::new(tcp_stream), svc)
+                    .await
+                    .unwrap();
+            });
+        }
+    });
+
+    for _ in 0..2 {
+        let tcp_stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let client_addr = tcp_stream.local_addr().unwrap();
+        let (mut send_request, conn) = conn::http1::handshake(TokioIoToHyper1Io::new(tcp_stream))
+            .await
+            .unwrap();
+        tokio::spawn(conn);
+
+        // every request on the connection sees the same info
+        for _ in 0..2 {
+            let req = hyper_1::Request::builder()
+                .uri("/")
+                .body(http_body_util::Empty::<Bytes>::new())
+                .unwrap();
+            let res = send_request.send_request(req).await.unwrap();
+            assert_eq!(res.status(), hyper_1::StatusCode::OK);
+            let collected = res.into_body().collect().await.unwrap();
+            assert_eq!(collected.to_bytes(), format!("{client_addr} {addr}"));
+        }
+    }
+}
+
+#[tokio::test]
+async fn add_connection_info_layer() {
+    use tower::{ServiceBuilder, ServiceExt};
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct TlsInfo(&'static str);
+
+    let svc = ServiceBuilder::new()
+        .layer(AddConnectionInfoLayer::new(TlsInfo("h2")))
+        .service_fn(|req: Request<hyper_014::Body>| async move {
+            let tls_info = req.extensions().get::<TlsInfo>().cloned();
+            Ok::<_, Infallible>(Response::new(hyper_014::Body::from(tls_info.unwrap().0)))
+        });
+
+    let res = svc
+        .oneshot(Request::new(hyper_014::Body::empty()))
+        .await
+        .unwrap();
+    let bytes = hyper_014::body::to_bytes(res).await.unwrap();
+    assert_eq!(bytes, "h2");
+}
+
+#[tokio::test]
+async fn serve_with_graceful_shutdown_completes_in_flight_requests() {
+    use std::time::Duration;
+    use tokio::sync::{mpsc, oneshot};
+
+    for http2 in [false, true] {
+        let (started_tx, mut started_rx) = mpsc::unbounded_channel::<()>();
+        let svc = tower::service_fn(move |_req: Request<_>| {
+            let started_tx = started_tx.clone();
+            async move {
+                started_tx.send(()).unwrap();
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                Ok::<_, Infallible>(Response::new(hyper_014::Body::from("done")))
+            }
+        });
+
+        let tcp_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = tcp_listener.local_addr().unwrap();
+        let (signal_tx, signal_rx) = oneshot::channel::<()>();
+        let server = tokio::spawn(server::serve_with_graceful_shutdown(
+            tcp_listener,
+            svc,
+            async move {
+                signal_rx.await.ok();
+            },
+        ));
+
+        let tcp_stream =
+            TokioIoToHyper1Io::new(tokio::net::TcpStream::connect(addr).await.unwrap());
+        let req = hyper_1::Request::builder()
+            .uri(format!("http://{addr}/"))
+            .body(http_body_util::Empty::<Bytes>::new())
+            .unwrap();
+        let res = if http2 {
+            let (mut send_request, conn) =
+                hyper_1::client::conn::http2::handshake(TokioExecutor::new(), tcp_stream)
+                    .await
+                    .unwrap();
+            tokio::spawn(conn);
+            tokio::spawn(async move { send_request.send_request(req).await })
+        } else {
+            let (mut send_request, conn) = hyper_1::client::conn::http1::handshake(tcp_stream)
+                .await
+                .unwrap();
+            tokio::spawn(conn);
+            tokio::spawn(async move { send_request.send_request(req).await })
+        };
+
+        started_rx.recv().await.unwrap();
+        signal_tx.send(()).unwrap();
+
+        let res = res.await.unwrap().unwrap();
+        assert_eq!(res.status(), hyper_1::StatusCode::OK);
+        let bytes = res.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(bytes, "done");
+
+        tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .expect("server didn't shut down")
+            .unwrap();
+
+        // the listener has been closed
+        assert!(tokio::net::TcpStream::connect(addr).await.is_err());
+    }
+}
+
+#[tokio::test]
+async fn server_serves_make_service() {
+    use axum::{extract::Extension, routing::get, Router};
+    use server::Server;
+    use std::future::IntoFuture;
+    use tokio::sync::oneshot;
+
+    async fn handler(Extension(info): Extension<ConnectionInfo>) -> String {
+        info.local_addr().to_string()
+    }
+
+    let app = Router::new().route("/", get(handler));
+
+    let server = Server::bind(&"127.0.0.1:0".parse().unwrap())
+        .http1_keepalive(true)
+        .tcp_nodelay(true)
+        .serve(app.clone().into_make_service());
+    let addr = server.local_addr();
+    let (signal_tx, signal_rx) = oneshot::channel::<()>();
+    let server = tokio::spawn(
+        server
+            .with_graceful_shutdown(async move {
+                signal_rx.await.ok();
+            })
+            .into_future(),
+    );
+
+    for http2_only in [false, true] {
+        let client = hyper_014::Client::builder()
+            .http2_only(http2_only)
+            .build_http::<hyper_014::Body>();
+        let res = client
+            .get(format!("http://{addr}").parse().unwrap())
+            .await
+            .unwrap();
+        let version = if http2_only {
+            http::Version::HTTP_2
+        } else {
+            http::Version::HTTP_11
+        };
+        assert_eq!(res.version(), version);
+        let bytes = hyper_014::body::to_bytes(res).await.unwrap();
+        assert_eq!(bytes, addr.to_string());
+    }
+
+    signal_tx.send(()).unwrap();
+    server.await.unwrap().unwrap();
+
+    // `http2_only` rejects HTTP/1 clients
+    let server = Server::bind(&"127.0.0.1:0".parse().unwrap())
+        .http2_only(true)
+        .serve(app.into_make_service());
+    let addr = server.local_addr();
+    tokio::spawn(server.into_future());
+
+    let client = hyper_014::Client::builder().build_http::<hyper_014::Body>();
+    let result = client.get(format!("http://{addr}").parse().unwrap()).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn server_serves_make_service_with_connect_info() {
+    use axum::{
+        extract::connect_info::{ConnectInfo, Connected},
+        routing::get,
+        Router,
+    };
+    use server::Server;
+    use std::{future::IntoFuture, net::SocketAddr};
+
+    #[derive(Clone, Debug)]
+    struct ClientAddr(SocketAddr);
+
+    impl Connected<ConnectionInfo> for ClientAddr {
+        fn connect_info(target: ConnectionInfo) -> Self {
+            ClientAddr(target.remote_addr())
+        }
+    }
+
+    async fn handler(ConnectInfo(ClientAddr(addr)): ConnectInfo<ClientAddr>) -> String {
+        addr.to_string()
+    }
+
+    let app = Router::new().route("/", get(handler));
+
+    let server = Server::bind(&"127.0.0.1:0".parse().unwrap())
+        .serve(app.into_make_service_with_connect_info::<ClientAddr>());
+    let addr = server.local_addr();
+    tokio::spawn(server.into_future());
+
+    let tcp_stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let client_addr = tcp_stream.local_addr().unwrap();
+    let (mut send_request, conn) = hyper_014::client::conn::handshake(tcp_stream)
+        .await
+        .unwrap();
+    tokio::spawn(conn);
+
+    let res = send_request
+        .send_request(Request::new(hyper_014::Body::empty()))
+        .await
+        .unwrap();
+    let bytes = hyper_014::body::to_bytes(res).await.unwrap();
+    assert_eq!(bytes, client_addr.to_string());
+}
+
+// hyper 1.0's requests use http 1.0, or http 0.2 with its release candidates
+trait OnUpgradeExt {
+    fn on_upgrade(&mut self) -> upgrade::OnUpgrade;
+}
+
+impl<B> OnUpgradeExt for http::Request<B> {
+    fn on_upgrade(&mut self) -> upgrade::OnUpgrade {
+        upgrade::on(self)
+    }
+}
+
+impl<B> OnUpgradeExt for http_1::Request<B> {
+    fn on_upgrade(&mut self) -> upgrade::OnUpgrade {
+        upgrade::on_http1(self)
+    }
+}
+
+fn spawn_echo(on_upgrade: upgrade::OnUpgrade) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    tokio::spawn(async move {
+        let mut upgraded = on_upgrade.await.unwrap();
+        let mut buf = [0; 5];
+        upgraded.read_exact(&mut buf).await.unwrap();
+        upgraded.write_all(&buf).await.unwrap();
+        upgraded.flush().await.unwrap();
+    });
+}
+
+async fn echo_upgrade_handler<B>(
+    mut req: Request<B>,
+) -> Result<Response<hyper_014::Body>, Infallible> {
+    spawn_echo(upgrade::on(&mut req));
+
+    let res = Response::builder()
+        .status(StatusCode::SWITCHING_PROTOCOLS)
+        .header(http::header::CONNECTION, "upgrade")
+        .header(http::header::UPGRADE, "echo")
+        .body(hyper_014::Body::empty())
+        .unwrap();
+    Ok(res)
+}
+
+async fn assert_echo_upgrade(addr: std::net::SocketAddr) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let client = hyper_014::Client::builder().build_http();
+    let res = client
+        .request(
+            Request::builder()
+                .uri(format!("http://{addr}"))
+                .header(http::header::CONNECTION, "upgrade")
+                .header(http::header::UPGRADE, "echo")
+                .body(hyper_014::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::SWITCHING_PROTOCOLS);
+
+    let mut upgraded = hyper_014::upgrade::on(res).await.unwrap();
+    upgraded.write_all(b"hello").await.unwrap();
+    let mut buf = [0; 5];
+    upgraded.read_exact(&mut buf).await.unwrap();
+    assert_eq!(&buf, b"hello");
+}
+
+#[tokio::test]
+async fn upgrade_through_tower_service_03_service_to_hyper_1_service() {
+    let svc = TowerService03HttpServiceAsHyper1HttpService::new(tower::service_fn(
+        echo_upgrade_handler::<HttpBody1ToHttpBody04<hyper_1::body::Incoming>>,
+    ));
+
+    let tcp_listener = TcpListener::bind("0.0.0.0:0").await.unwrap();
+    let addr = tcp_listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (tcp_stream, _) = tcp_listener.accept().await.unwrap();
+        let tcp_stream = TokioIoToHyper1Io::new(tcp_stream);
+        http1::Builder::new()
+            .serve_connection(tcp_stream, svc)
+            .with_upgrades()
+            .await
+            .unwrap();
+    });
+
+    assert_echo_upgrade(addr).await;
+}
+
+#[tokio::test]
+async fn upgrade_through_hyper_1_service_to_tower_service_03_service() {
+    let svc = Hyper1HttpServiceAsTowerService03HttpService::new(hyper_1::service::service_fn(
+        |mut req: hyper_1::Request<HttpBody04ToHttpBody1<hyper_014::Body>>| async move {
+            spawn_echo(req.on_upgrade());
+
+            let res = hyper_1::Response::builder()
+                .status(hyper_1::StatusCode::SWITCHING_PROTOCOLS)
+                .header(hyper_1::header::CONNECTION, "upgrade")
+                .header(hyper_1::header::UPGRADE, "echo")
+                .body(http_body_util::Empty::<Bytes>::new())
+                .unwrap();
+            Ok::<_, Infallible>(res)
+        },
+    ));
+
+    let tcp_listener = std::net::TcpListener::bind("0.0.0.0:0").unwrap();
+    let addr = tcp_listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        hyper_014::Server::from_tcp(tcp_listener)
+            .unwrap()
+            .serve(tower::make::Shared::new(svc))
+            .await
+            .unwrap();
+    });
+
+    assert_echo_upgrade(addr).await;
+}
+
+#[tokio::test]
+async fn axum_web_socket_upgrade_through_tower_service_03_service_to_hyper_1_service() {
+    use axum::{
+        extract::ws::{Message, WebSocket, WebSocketUpgrade},
+        response::Response as AxumResponse,
+        routing::get,
+        Router,
+    };
+    use futures_util::{SinkExt, StreamExt};
+
+    async fn handler(ws: WebSocketUpgrade) -> AxumResponse {
+        ws.on_upgrade(|mut socket: WebSocket| async move {
+            while let Some(Ok(msg)) = socket.recv().await {
+                if let Message::Text(text) = msg {
+                    socket
+                        .send(Message::Text(format!("echo: {text}")))
+                        .await
+                        .unwrap();
+                }
+            }
+        })
+    }
+
+    let app = Router::new().route("/ws", get(handler));
+    let svc =
+        TowerService03HttpServiceAsHyper1HttpService::new(upgrade::AddHyper014OnUpgrade::new(app));
+
+    let tcp_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = tcp_listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (tcp_stream, _) = tcp_listener.accept().await.unwrap();
+        http1::Builder::new()
+            .serve_connection(TokioIoToHyper1Io::new(tcp_stream), svc)
+            .with_upgrades()
+            .await
+            .unwrap();
+    });
+
+    let (mut socket, res) = tokio_tungstenite::connect_async(format!("ws://{addr}/ws"))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::SWITCHING_PROTOCOLS);
+
+    for text in ["hello", "world"] {
+        socket
+            .send(tokio_tungstenite::tungstenite::Message::Text(
+                text.to_owned(),
+            ))
+            .await
+            .unwrap();
+        let msg = socket.next().await.unwrap().unwrap();
+        assert_eq!(msg.into_text().unwrap(), format!("echo: {text}"));
+    }
+    socket.close(None).await.unwrap();
+}
+
+#[tokio::test]
+async fn io_adapters_round_trip() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let (client, server) = tokio::io::duplex(64);
+
+    // tokio -> hyper 1.0 -> tokio
+    let mut client = Hyper1IoToTokioIo::new(TokioIoToHyper1Io::new(client));
+    let mut server = Hyper1IoToTokioIo::new(TokioIoToHyper1Io::new(server));
+
+    client.write_all(b"ping").await.unwrap();
+    let mut buf = [0; 4];
+    server.read_exact(&mut buf).await.unwrap();
+    assert_eq!(&buf, b"ping");
+
+    server.write_all(b"pong").await.unwrap();
+    server.shutdown().await.unwrap();
+    let mut buf = Vec::new();
+    client.read_to_end(&mut buf).await.unwrap();
+    assert_eq!(buf, b"pong");
+}
+
+#[tokio::test]
+async fn serving_http2_with_tokio_executor_and_timer() {
+    use hyper_1::server::conn::http2;
+    use std::time::Duration;
+
+    async fn handle<B>(_req: Request<B>) -> Result<Response<hyper_014::Body>, Infallible> {
+        Ok(Response::new(hyper_014::Body::from("out")))
+    }
+
+    let svc = TowerService03HttpServiceAsHyper1HttpService::new(tower::service_fn(handle));
+
+    let tcp_listener = TcpListener::bind("0.0.0.0:0").await.unwrap();
+    let addr = tcp_listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (tcp_stream, _) = tcp_listener.accept().await.unwrap();
+        http2::Builder::new(TokioExecutor::new())
+            .timer(TokioTimer::new())
+            .keep_alive_interval(Duration::from_secs(10))
+            .serve_connection(TokioIoToHyper1Io::new(tcp_stream), svc)
+            .await
+            .unwrap();
+    });
+
+    let client = hyper_014::Client::builder()
+        .http2_only(true)
+        .build_http::<hyper_014::Body>();
+    let res = client
+        .get(format!("http://{addr}").parse().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(res.version(), http::Version::HTTP_2);
+    let bytes = hyper_014::body::to_bytes(res).await.unwrap();
+    assert_eq!(bytes, "out");
+}
+
+#[test]
+fn serving_http2_with_other_executors() {
+    use hyper_1::server::conn::http2;
+    use std::{
+        future::Future,
+        net::SocketAddr,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    #[derive(Clone)]
+    struct CountingExecutor(Arc<AtomicUsize>);
+
+    impl<F> hyper_014::rt::Executor<F> for CountingExecutor
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        fn execute(&self, fut: F) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(fut);
+        }
+    }
+
+    // responds with the name of the thread the request is handled on
+    async fn handle<B>(_req: Request<B>) -> Result<Response<hyper_014::Body>, Infallible> {
+        let name = std::thread::current().name().unwrap_or_default().to_owned();
+        Ok(Response::new(hyper_014::Body::from(name)))
+    }
+
+    async fn get(addr: SocketAddr) -> Bytes {
+        let tcp_stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        let (mut send_request, conn) = hyper_1::client::conn::http2::handshake(
+            TokioExecutor::new(),
+            TokioIoToHyper1Io::new(tcp_stream),
+        )
+        .await
+        .unwrap();
+        tokio::spawn(conn);
+
+        let req = hyper_1::Request::builder()
+            .uri(format!("http://{addr}/"))
+            .body(http_body_util::Empty::<Bytes>::new())
+            .unwrap();
+        let res = send_request.send_request(req).await.unwrap();
+        res.into_body().collect().await.unwrap().to_bytes()
+    }
+
+    let svc = TowerService03HttpServiceAsHyper1HttpService::new(tower::service_fn(handle));
+
+    let other_runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name("other-runtime")
+        .enable_all()
+        .build()
+        .unwrap();
+
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(async {
+            // requests are handled on the runtime of the handle
+            let executor = TokioExecutor::with_handle(other_runtime.handle().clone());
+            let tcp_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = tcp_listener.local_addr().unwrap();
+            tokio::spawn(async move {
+                let (tcp_stream, _) = tcp_listener.accept().await.unwrap();
+                http2::Builder::new(executor)
+                    .serve_connection(TokioIoToHyper1Io::new(tcp_stream), svc)
+                    .await
+                    .unwrap();
+            });
+            assert_eq!(get(addr).await, "other-runtime");
+
+            // requests are handled by futures spawned with the hyper 0.14 executor
+            let spawned = Arc::new(AtomicUsize::new(0));
+            let executor = Hyper014ExecutorAsHyper1Executor::new(CountingExecutor(spawned.clone()));
+            let tcp_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = tcp_listener.local_addr().unwrap();
+            tokio::spawn(async move {
+                let (tcp_stream, _) = tcp_listener.accept().await.unwrap();
+                http2::Builder::new(executor)
+                    .serve_connection(TokioIoToHyper1Io::new(tcp_stream), svc)
+                    .await
+                    .unwrap();
+            });
+            assert_ne!(get(addr).await, "other-runtime");
+            assert!(spawned.load(Ordering::SeqCst) > 0);
+        });
+
+    other_runtime.shutdown_background();
+}
+
+#[tokio::test]
+async fn make_service_receives_connection_info() {
+    use axum::extract::connect_info::{ConnectInfo, Connected};
+    use hyper_1::service::Service as _;
+    use std::net::SocketAddr;
+
+    #[derive(Clone, Debug)]
+    struct ClientAddr(SocketAddr);
+
+    impl Connected<SocketAddr> for ClientAddr {
+        fn connect_info(target: SocketAddr) -> Self {
+            ClientAddr(target)
+        }
+    }
+
+    let app = axum::Router::new().route(
+        "/",
+        axum::routing::get(
+            |ConnectInfo(ClientAddr(addr)): ConnectInfo<ClientAddr>| async move { addr.to_string() },
+        ),
+    );
+    let make_service = TowerService03MakeServiceAsHyper1MakeService::new(
+        app.into_make_service_with_connect_info::<ClientAddr>(),
+    );
+
+    let tcp_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = tcp_listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (tcp_stream, remote_addr) = tcp_listener.accept().await.unwrap();
+        let svc = make_service.call(remote_addr).await.unwrap();
+        http1::Builder::new()
+            .serve_connection(TokioIoToHyper1Io::new(tcp_stream), svc)
+            .await
+            .unwrap();
+    });
+
+    let tcp_stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let local_addr = tcp_stream.local_addr().unwrap();
+    let (mut send_request, conn) =
+        hyper_1::client::conn::http1::handshake(TokioIoToHyper1Io::new(tcp_stream))
+            .await
+            .unwrap();
+    tokio::spawn(conn);
+
+    let res = send_request
+        .send_request(
+            hyper_1::Request::builder()
+                .uri("/")
+                .body(http_body_util::Empty::<Bytes>::new())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.status(), hyper_1::StatusCode::OK);
+    let bytes = res.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(bytes, local_addr.to_string());
+}
+
+#[tokio::test]
+async fn connectors_preserve_connection_metadata() {
+    use hyper_014::client::{
+        connect::{capture_connection, Connection, HttpInfo},
+        HttpConnector,
+    };
+    use tower::ServiceExt;
+
+    async fn handle<B>(_req: Request<B>) -> Result<Response<hyper_014::Body>, Infallible> {
+        Ok(Response::new(hyper_014::Body::from("out")))
+    }
+
+    let tcp_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = tcp_listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        loop {
+            let (tcp_stream, _) = tcp_listener.accept().await.unwrap();
+            let svc = TowerService03HttpServiceAsHyper1HttpService::new(tower::service_fn(handle));
+            tokio::spawn(async move {
+                http1::Builder::new()
+                    .serve_connection(TokioIoToHyper1Io::new(tcp_stream), svc)
+                    .await
+                    .unwrap();
+            });
+        }
+    });
+    let uri: http::Uri = format!("http://{addr}").parse().unwrap();
+
+    // hyper 0.14 connector used with a hyper 1.0 client connection
+    let io = Hyper014ConnectorAsHyper1Connector::new(HttpConnector::new())
+        .oneshot(hyper_http::uri_to_hyper(uri.clone()))
+        .await
+        .unwrap();
+    let mut extensions = http::Extensions::new();
+    io.connected().get_extras(&mut extensions);
+    assert_eq!(extensions.get::<HttpInfo>().unwrap().remote_addr(), addr);
+
+    let (mut send_request, conn) = hyper_1::client::conn::http1::handshake(io).await.unwrap();
+    tokio::spawn(conn);
+    let res = send_request
+        .send_request(
+            hyper_1::Request::builder()
+                .uri("/")
+                .body(http_body_util::Empty::<Bytes>::new())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let bytes = res.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(bytes, "out");
+
+    // and back to a hyper 0.14 connector
+    let connector = Hyper1ConnectorAsHyper014Connector::new(
+        Hyper014ConnectorAsHyper1Connector::new(HttpConnector::new()),
+    );
+    let client = hyper_014::Client::builder().build::<_, hyper_014::Body>(connector);
+    let mut req = Request::builder()
+        .uri(uri)
+        .body(hyper_014::Body::empty())
+        .unwrap();
+    let captured = capture_connection(&mut req);
+    let res = client.request(req).await.unwrap();
+    let bytes = hyper_014::body::to_bytes(res).await.unwrap();
+    assert_eq!(bytes, "out");
+
+    let connected = captured.connection_metadata();
+    let connected = connected.as_ref().unwrap();
+    assert!(!connected.is_proxied());
+    let mut extensions = http::Extensions::new();
+    connected.get_extras(&mut extensions);
+    assert_eq!(extensions.get::<HttpInfo>().unwrap().remote_addr(), addr);
+}
+
+#[tokio::test]
+async fn hyper_014_client_with_hyper_1_connector() {
+    use hyper_014::client::connect::{capture_connection, Connected, Connection};
+    use std::{
+        io,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    // an IO object that only implements hyper 1.0's IO traits
+    struct Hyper1Io(TokioIoToHyper1Io<tokio::net::TcpStream>);
+
+    impl hyper_1::rt::Read for Hyper1Io {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: hyper_1::rt::ReadBufCursor<'_>,
+        ) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.0).poll_read(cx, buf)
+        }
+    }
+
+    impl hyper_1::rt::Write for Hyper1Io {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            hyper_1::rt::Write::poll_write(Pin::new(&mut self.0), cx, buf)
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            hyper_1::rt::Write::poll_flush(Pin::new(&mut self.0), cx)
+        }
+
+        fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            hyper_1::rt::Write::poll_shutdown(Pin::new(&mut self.0), cx)
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct ConnectedTo(String);
+
+    impl Connection for Hyper1Io {
+        fn connected(&self) -> Connected {
+            Connected::new().extra(ConnectedTo("hyper-1".to_owned()))
+        }
+    }
+
+    async fn handle<B>(_req: Request<B>) -> Result<Response<hyper_014::Body>, Infallible> {
+        Ok(Response::new(hyper_014::Body::from("out")))
+    }
+
+    let tcp_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = tcp_listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (tcp_stream, _) = tcp_listener.accept().await.unwrap();
+        let svc = TowerService03HttpServiceAsHyper1HttpService::new(tower::service_fn(handle));
+        http1::Builder::new()
+            .serve_connection(TokioIoToHyper1Io::new(tcp_stream), svc)
+            .await
+            .unwrap();
+    });
+
+    // a hyper 1.0 connector is called with http 1.0's `Uri`
+    let connector = tower::service_fn(|uri: hyper_1::Uri| {
+        Box::pin(async move {
+            let authority = uri.authority().unwrap().as_str();
+            let tcp_stream = tokio::net::TcpStream::connect(authority).await?;
+            Ok::<_, io::Error>(Hyper1Io(TokioIoToHyper1Io::new(tcp_stream)))
+        })
+    });
+    let client = hyper_014::Client::builder()
+        .build::<_, hyper_014::Body>(Hyper1ConnectorAsHyper014Connector::new(connector));
+
+    let mut req = Request::builder()
+        .uri(format!("http://{addr}"))
+        .body(hyper_014::Body::empty())
+        .unwrap();
+    let captured = capture_connection(&mut req);
+    let res = client.request(req).await.unwrap();
+    let bytes = hyper_014::body::to_bytes(res).await.unwrap();
+    assert_eq!(bytes, "out");
+
+    let connected = captured.connection_metadata();
+    let mut extensions = http::Extensions::new();
+    connected.as_ref().unwrap().get_extras(&mut extensions);
+    assert_eq!(
+        extensions.get::<ConnectedTo>(),
+        Some(&ConnectedTo("hyper-1".to_owned()))
+    );
+}
+
+#[tokio::test]
+async fn send_request_as_tower_service() {
+    use hyper_1::{client::conn, server::conn::http2};
+    use std::time::Duration;
+    use tower::{Service, ServiceBuilder, ServiceExt};
+
+    async fn handle<B>(req: Request<B>) -> Result<Response<hyper_014::Body>, Infallible>
+    where
+        B: http_body_04::Body,
+    {
+        let bytes = hyper_014::body::to_bytes(req)
+            .await
+            .unwrap_or_else(|_| panic!());
+        Ok(Response::new(hyper_014::Body::from(bytes)))
+    }
+
+    let tcp_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = tcp_listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (tcp_stream, _) = tcp_listener.accept().await.unwrap();
+        let svc = TowerService03HttpServiceAsHyper1HttpService::new(tower::service_fn(handle));
+        http1::Builder::new()
+            .serve_connection(TokioIoToHyper1Io::new(tcp_stream), svc)
+            .await
+            .unwrap();
+        let (tcp_stream, _) = tcp_listener.accept().await.unwrap();
+        let svc = TowerService03HttpServiceAsHyper1HttpService::new(tower::service_fn(handle));
+        http2::Builder::new(TokioExecutor::new())
+            .serve_connection(TokioIoToHyper1Io::new(tcp_stream), svc)
+            .await
+            .unwrap();
+    });
+
+    // http1
+    let tcp_stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let (send_request, conn) = conn::http1::handshake(TokioIoToHyper1Io::new(tcp_stream))
+        .await
+        .unwrap();
+    let conn = tokio::spawn(conn);
+    let mut client = ServiceBuilder::new()
+        .timeout(Duration::from_secs(10))
+        .service(Hyper1SendRequestAsTowerService03HttpService::new(
+            send_request,
+        ));
+    for _ in 0..2 {
+        let req = Request::builder()
+            .uri("/")
+            .body(hyper_014::Body::from("http1"))
+            .unwrap();
+        let res = client.ready().await.unwrap().call(req).await.unwrap();
+        let bytes = hyper_014::body::to_bytes(res).await.unwrap();
+        assert_eq!(bytes, "http1");
+    }
+    drop(client);
+    conn.await.unwrap().unwrap();
+
+    // http2
+    let tcp_stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let (send_request, conn) =
+        conn::http2::handshake(TokioExecutor::new(), TokioIoToHyper1Io::new(tcp_stream))
+            .await
+            .unwrap();
+    tokio::spawn(conn);
+    let client = Hyper1SendRequestAsTowerService03HttpService::new(send_request);
+    let req = Request::builder()
+        .uri(format!("http://{addr}"))
+        .body(hyper_014::Body::from("http2"))
+        .unwrap();
+    let res = client.oneshot(req).await.unwrap();
+    assert_eq!(res.version(), http::Version::HTTP_2);
+    let bytes = hyper_014::body::to_bytes(res).await.unwrap();
+    assert_eq!(bytes, "http2");
+}
+
+#[test]
+fn http_02_and_http_1_round_trip() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct Marker(&'static str);
+
+    let mut sensitive = http::HeaderValue::from_static("secret");
+    sensitive.set_sensitive(true);
+    let req = Request::builder()
+        .method(http::Method::PATCH)
+        .uri("https://example.com/path?query=1")
+        .version(http::Version::HTTP_2)
+        .header("x-multi", "one")
+        .header("x-multi", "two")
+        .header(http::header::AUTHORIZATION, sensitive)
+        .extension(Marker("0.2"))
+        .body(())
+        .unwrap();
+
+    let mut req = convert::request_to_http1(req);
+    assert_eq!(req.method(), http_1::Method::PATCH);
+    assert_eq!(req.uri(), "https://example.com/path?query=1");
+    assert_eq!(req.version(), http_1::Version::HTTP_2);
+    let values = req.headers().get_all("x-multi").iter().collect::<Vec<_>>();
+    assert_eq!(values, ["one", "two"]);
+    assert!(req.headers()[http_1::header::AUTHORIZATION].is_sensitive());
+    let http02 = req.extensions().get::<convert::Http02Extensions>().unwrap();
+    assert_eq!(http02.get::<Marker>(), Some(&Marker("0.2")));
+    req.extensions_mut().insert(Marker("1.0"));
+
+    let req = convert::request_from_http1(req);
+    assert_eq!(req.method(), http::Method::PATCH);
+    assert_eq!(req.extensions().get::<Marker>(), Some(&Marker("0.2")));
+    let http1 = req.extensions().get::<convert::Http1Extensions>().unwrap();
+    assert_eq!(http1.get::<Marker>(), Some(&Marker("1.0")));
+
+    let res = http_1::Response::builder()
+        .status(http_1::StatusCode::IM_A_TEAPOT)
+        .header("x-foo", "bar")
+        .body(())
+        .unwrap();
+    let res = convert::response_from_http1(res);
+    assert_eq!(res.status(), StatusCode::IM_A_TEAPOT);
+    assert_eq!(res.headers()["x-foo"], "bar");
+    assert!(res.extensions().get::<convert::Http1Extensions>().is_none());
+}
+
+#[test]
+fn http_02_and_http_1_uri_forms() {
+    for uri in [
+        "https://example.com/path?query=1",
+        "http://user@example.com:8080",
+        "/path?query=1",
+        "/",
+        "example.com:443",
+        "*",
+    ] {
+        let http1 = convert::uri_to_http1(uri.parse().unwrap());
+        assert_eq!(http1, uri);
+        assert_eq!(
+            http1.to_string(),
+            uri.parse::<http::Uri>().unwrap().to_string()
+        );
+
+        let http02 = convert::uri_from_http1(uri.parse().unwrap());
+        assert_eq!(http02, uri);
+        assert_eq!(
+            http02.to_string(),
+            uri.parse::<http_1::Uri>().unwrap().to_string()
+        );
+    }
+}
+
+#[tokio::test]
+async fn http_02_service_as_http_1_service() {
+    use tower::{ServiceBuilder, ServiceExt};
+
+    let svc = ServiceBuilder::new()
+        .layer(Http02ServiceAsHttp1ServiceLayer::new())
+        .service_fn(|req: Request<&'static str>| async move {
+            assert_eq!(req.headers()["x-foo"], "bar");
+            Ok::<_, Infallible>(
+                Response::builder()
+                    .status(StatusCode::CREATED)
+                    .body(req.into_body())
+                    .unwrap(),
+            )
+        });
+
+    let req = http_1::Request::builder()
+        .header("x-foo", "bar")
+        .body("body")
+        .unwrap();
+    let res: http_1::Response<&'static str> = svc.oneshot(req).await.unwrap();
+    assert_eq!(res.status(), http_1::StatusCode::CREATED);
+    assert_eq!(res.into_body(), "body");
+}
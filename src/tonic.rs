@@ -0,0 +1,166 @@
+//! Serving and calling [tonic] gRPC services over hyper 1.0.
+//!
+//! tonic 0.9 and 0.10 are built on hyper 0.14, http 0.2 and tower-service 0.3. The services
+//! generated by `tonic-build` accept any request body, but tonic's `Routes`, which combines
+//! several services into one, only accepts hyper 0.14's `Body`. Wrap it in [`IntoHyper014Body`]
+//! to serve it with [`TowerService03HttpServiceAsHyper1HttpService`].
+//!
+//! On the client side, tonic's clients accept any tower-service 0.3 `Service`, so a hyper 1.0
+//! http2 connection can be used through [`Hyper1SendRequestAsTowerService03HttpService`] without
+//! further conversions.
+//!
+//! gRPC sends the `grpc-status` of a call in the response trailers, which the body adapters
+//! forward between the two versions of hyper.
+//!
+//! This module requires the `hyper-014` feature.
+//!
+//! # Example
+//!
+//! Serving tonic's `Routes` with hyper 1.0:
+//!
+//! ```no_run
+//! # use hyper_1 as hyper;
+//! use hyper::server::conn::http2;
+//! use tokio::net::TcpListener;
+//! use tonic_010::transport::server::Routes;
+//! use tower_hyper_http_body_compat::{
+//!     tonic::IntoHyper014Body, TokioExecutor, TokioIoToHyper1Io,
+//!     TowerService03HttpServiceAsHyper1HttpService,
+//! };
+//!
+//! # fn services() -> Routes { Routes::default() }
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//!     // the services generated by `tonic-build`
+//!     let routes: Routes = services();
+//!
+//!     let service = TowerService03HttpServiceAsHyper1HttpService::new(
+//!         IntoHyper014Body::new(routes),
+//!     );
+//!
+//!     let tcp_listener = TcpListener::bind("127.0.0.1:50051").await?;
+//!     loop {
+//!         let (tcp_stream, _) = tcp_listener.accept().await?;
+//!         let service = service.clone();
+//!         tokio::spawn(async move {
+//!             if let Err(http_err) = http2::Builder::new(TokioExecutor::new())
+//!                 .serve_connection(TokioIoToHyper1Io::new(tcp_stream), service)
+//!                 .await
+//!             {
+//!                 eprintln!("Error while serving HTTP connection: {}", http_err);
+//!             }
+//!         });
+//!     }
+//! }
+//! ```
+//!
+//! Calling a gRPC service with a hyper 1.0 client:
+//!
+//! ```no_run
+//! # use hyper_1 as hyper;
+//! use hyper::client::conn::http2;
+//! use tokio::net::TcpStream;
+//! use tower_hyper_http_body_compat::{
+//!     Hyper1SendRequestAsTowerService03HttpService, TokioExecutor, TokioIoToHyper1Io,
+//! };
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//!     let tcp_stream = TcpStream::connect("127.0.0.1:50051").await?;
+//!     let (send_request, conn) =
+//!         http2::handshake(TokioExecutor::new(), TokioIoToHyper1Io::new(tcp_stream)).await?;
+//!     tokio::spawn(conn);
+//!
+//!     let channel = Hyper1SendRequestAsTowerService03HttpService::new(send_request);
+//!
+//!     // hyper 1.0 requires absolute URIs for http2 so the client must be given an origin. Clients
+//!     // generated by `tonic-build` are created the same way with `with_origin`.
+//!     let mut client =
+//!         tonic_010::client::Grpc::with_origin(channel, "http://127.0.0.1:50051".parse()?);
+//!     client.ready().await?;
+//!     // ...
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! [tonic]: https://docs.rs/tonic/0.10
+//! [`TowerService03HttpServiceAsHyper1HttpService`]: crate::TowerService03HttpServiceAsHyper1HttpService
+//! [`Hyper1SendRequestAsTowerService03HttpService`]: crate::Hyper1SendRequestAsTowerService03HttpService
+
+use std::task::{Context, Poll};
+
+use http::Request;
+
+use crate::AsHyper014Body;
+
+/// A [tower-service 0.3 `Service`] that converts request bodies to hyper 0.14's `Body`.
+///
+/// This allows serving services that require hyper 0.14's `Body`, such as tonic's `Routes`,
+/// with [`TowerService03HttpServiceAsHyper1HttpService`].
+///
+/// Request bodies are converted with [`AsHyper014Body`], which reads hyper 1.0's `Incoming`
+/// directly. This covers the request bodies of [`TowerService03HttpServiceAsHyper1HttpService`].
+/// Request trailers are dropped since hyper 0.14's `Body` cannot be created from a stream with
+/// trailers. gRPC requests never have trailers.
+///
+/// [tower-service 0.3 `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
+/// [`TowerService03HttpServiceAsHyper1HttpService`]: crate::TowerService03HttpServiceAsHyper1HttpService
+#[derive(Clone, Copy, Debug)]
+pub struct IntoHyper014Body<S> {
+    service: S,
+}
+
+impl<S> IntoHyper014Body<S> {
+    /// Create a new `IntoHyper014Body`.
+    #[inline]
+    pub fn new(service: S) -> Self {
+        Self { service }
+    }
+}
+
+impl<S, B> tower_service_03::Service<Request<B>> for IntoHyper014Body<S>
+where
+    S: tower_service_03::Service<Request<hyper_014::Body>>,
+    B: AsHyper014Body,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    #[inline]
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let req = req.map(AsHyper014Body::into_hyper014_body);
+        self.service.call(req)
+    }
+}
+
+/// A [tower-layer 0.3 `Layer`] that produces [`IntoHyper014Body`]s.
+///
+/// [tower-layer 0.3 `Layer`]: https://docs.rs/tower-layer/latest/tower_layer/trait.Layer.html
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IntoHyper014BodyLayer {
+    _priv: (),
+}
+
+impl IntoHyper014BodyLayer {
+    /// Create a new `IntoHyper014BodyLayer`.
+    #[inline]
+    pub fn new() -> Self {
+        Self { _priv: () }
+    }
+}
+
+impl<S> tower_layer_03::Layer<S> for IntoHyper014BodyLayer {
+    type Service = IntoHyper014Body<S>;
+
+    #[inline]
+    fn layer(&self, inner: S) -> Self::Service {
+        IntoHyper014Body::new(inner)
+    }
+}
@@ -0,0 +1,598 @@
+//! HTTP upgrades (such as WebSockets or `CONNECT`) through the `Service` adapters.
+//!
+//! hyper 0.14 and hyper 1.0 each store their own `OnUpgrade` type in the request extensions,
+//! and neither can be constructed outside of hyper. That means code calling
+//! `hyper::upgrade::on` from one version cannot find the upgrade provided by the other version,
+//! so a hyper 0.14 based service running behind [`TowerService03HttpServiceAsHyper1HttpService`]
+//! cannot upgrade the connection using hyper 0.14's API.
+//!
+//! Instead, use [`on`] (for http 0.2 requests) or [`on_http1`] (for http 1.0 requests) from this
+//! module which work with upgrades from either version of hyper, including upgrades stored in
+//! [`Http1Extensions`] or [`Http02Extensions`] by the service adapters. They yield an
+//! [`Upgraded`] IO object that implements both tokio's `AsyncRead`/`AsyncWrite` (as used by
+//! hyper 0.14) and hyper 1.0's `rt::Read`/`rt::Write`.
+//!
+//! Code that calls hyper 0.14's `hyper::upgrade::on` directly, such as axum 0.6's
+//! `WebSocketUpgrade` extractor, can't be changed to use this module. Wrap it in
+//! [`AddHyper014OnUpgrade`] to make upgrades received by hyper 1.0 available to it.
+//!
+//! Support for hyper 0.14's upgrades requires the `hyper-014` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use http::{header, Request, Response, StatusCode};
+//! use std::convert::Infallible;
+//! use tokio::io::{AsyncReadExt, AsyncWriteExt};
+//! use tower_hyper_http_body_compat::upgrade;
+//!
+//! // echo everything sent over the upgraded connection
+//! async fn handler<B>(mut req: Request<B>) -> Result<Response<hyper_014::Body>, Infallible> {
+//!     let on_upgrade = upgrade::on(&mut req);
+//!
+//!     tokio::spawn(async move {
+//!         let mut upgraded = on_upgrade.await.unwrap();
+//!         let mut buf = [0; 1024];
+//!         loop {
+//!             let n = upgraded.read(&mut buf).await.unwrap();
+//!             if n == 0 {
+//!                 break;
+//!             }
+//!             upgraded.write_all(&buf[..n]).await.unwrap();
+//!         }
+//!     });
+//!
+//!     let res = Response::builder()
+//!         .status(StatusCode::SWITCHING_PROTOCOLS)
+//!         .header(header::CONNECTION, "upgrade")
+//!         .header(header::UPGRADE, "echo")
+//!         .body(hyper_014::Body::empty())
+//!         .unwrap();
+//!     Ok(res)
+//! }
+//! ```
+//!
+//! [`TowerService03HttpServiceAsHyper1HttpService`]: crate::TowerService03HttpServiceAsHyper1HttpService
+//! [`Http1Extensions`]: crate::convert::Http1Extensions
+//! [`Http02Extensions`]: crate::convert::Http02Extensions
+
+use std::{
+    fmt,
+    future::Future,
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+#[cfg(feature = "hyper-014")]
+use pin_project_lite::pin_project;
+use tower::BoxError;
+
+use crate::{convert::Http1Extensions, io::poll_read_hyper1_as_tokio};
+
+/// Get a future that resolves to the upgraded connection of an http 0.2 request.
+///
+/// This removes hyper 1.0's `OnUpgrade`, which the service adapters store in
+/// [`Http1Extensions`], or, if the `hyper-014` feature is enabled and the request was received
+/// by hyper 0.14, hyper 0.14's `OnUpgrade` from the request extensions.
+///
+/// The release candidates of hyper 1.0 use http 0.2 so their `OnUpgrade` is removed from the
+/// request extensions directly.
+///
+/// The returned future resolves to an error if the request doesn't have an upgrade.
+pub fn on<B>(req: &mut http::Request<B>) -> OnUpgrade {
+    let extensions = req.extensions_mut();
+
+    if let Some(on_upgrade) = take_hyper_1_on_upgrade(extensions) {
+        return OnUpgrade::hyper_1(on_upgrade);
+    }
+
+    #[cfg(feature = "hyper-014")]
+    if let Some(on_upgrade) = extensions.remove::<hyper_014::upgrade::OnUpgrade>() {
+        return OnUpgrade::hyper_014(on_upgrade);
+    }
+
+    OnUpgrade::none()
+}
+
+// the service adapters store hyper 1.0's extensions in `Http1Extensions`, unless hyper 1.0 uses
+// http 0.2 as well
+fn take_hyper_1_on_upgrade(
+    extensions: &mut http::Extensions,
+) -> Option<hyper_1::upgrade::OnUpgrade> {
+    extensions
+        .get_mut::<Http1Extensions>()
+        .and_then(|extensions| extensions.remove::<hyper_1::upgrade::OnUpgrade>())
+        .or_else(|| extensions.remove::<hyper_1::upgrade::OnUpgrade>())
+}
+
+#[cfg(feature = "hyper-014")]
+fn has_hyper_1_on_upgrade(extensions: &http::Extensions) -> bool {
+    extensions
+        .get::<Http1Extensions>()
+        .is_some_and(|extensions| extensions.get::<hyper_1::upgrade::OnUpgrade>().is_some())
+        || extensions.get::<hyper_1::upgrade::OnUpgrade>().is_some()
+}
+
+/// Get a future that resolves to the upgraded connection of an http 1.0 request.
+///
+/// This removes hyper 1.0's `OnUpgrade` from the request extensions or, if the `hyper-014`
+/// feature is enabled and the request was received by hyper 0.14, hyper 0.14's `OnUpgrade`
+/// stored in [`Http02Extensions`](crate::convert::Http02Extensions).
+///
+/// The returned future resolves to an error if the request doesn't have an upgrade.
+pub fn on_http1<B>(req: &mut http_1::Request<B>) -> OnUpgrade {
+    let extensions = req.extensions_mut();
+
+    if let Some(on_upgrade) = extensions.remove::<hyper_1::upgrade::OnUpgrade>() {
+        return OnUpgrade::hyper_1(on_upgrade);
+    }
+
+    #[cfg(feature = "hyper-014")]
+    {
+        let hyper_014 = extensions
+            .get_mut::<crate::convert::Http02Extensions>()
+            .and_then(|extensions| extensions.remove::<hyper_014::upgrade::OnUpgrade>());
+        if let Some(on_upgrade) = hyper_014 {
+            return OnUpgrade::hyper_014(on_upgrade);
+        }
+    }
+
+    OnUpgrade::none()
+}
+
+/// A future for a possible HTTP upgrade.
+///
+/// Created with [`on`] or [`on_http1`].
+pub struct OnUpgrade {
+    kind: OnUpgradeKind,
+}
+
+enum OnUpgradeKind {
+    Hyper1(hyper_1::upgrade::OnUpgrade),
+    #[cfg(feature = "hyper-014")]
+    Hyper014(hyper_014::upgrade::OnUpgrade),
+    None,
+}
+
+impl OnUpgrade {
+    fn hyper_1(on_upgrade: hyper_1::upgrade::OnUpgrade) -> Self {
+        Self {
+            kind: OnUpgradeKind::Hyper1(on_upgrade),
+        }
+    }
+
+    #[cfg(feature = "hyper-014")]
+    fn hyper_014(on_upgrade: hyper_014::upgrade::OnUpgrade) -> Self {
+        Self {
+            kind: OnUpgradeKind::Hyper014(on_upgrade),
+        }
+    }
+
+    fn none() -> Self {
+        Self {
+            kind: OnUpgradeKind::None,
+        }
+    }
+}
+
+impl fmt::Debug for OnUpgrade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OnUpgrade").finish()
+    }
+}
+
+impl Future for OnUpgrade {
+    type Output = Result<Upgraded, BoxError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match &mut self.kind {
+            OnUpgradeKind::Hyper1(on_upgrade) => {
+                let upgraded = ready!(Pin::new(on_upgrade).poll(cx))?;
+                Poll::Ready(Ok(Upgraded {
+                    kind: UpgradedKind::Hyper1(upgraded),
+                }))
+            }
+            #[cfg(feature = "hyper-014")]
+            OnUpgradeKind::Hyper014(on_upgrade) => {
+                let upgraded = ready!(Pin::new(on_upgrade).poll(cx))?;
+                Poll::Ready(Ok(Upgraded {
+                    kind: UpgradedKind::Hyper014(upgraded, Default::default()),
+                }))
+            }
+            OnUpgradeKind::None => Poll::Ready(Err(NoUpgrade.into())),
+        }
+    }
+}
+
+/// An upgraded HTTP connection.
+///
+/// Implements both tokio's `AsyncRead`/`AsyncWrite` and hyper 1.0's `rt::Read`/`rt::Write`,
+/// regardless of which version of hyper performed the upgrade.
+pub struct Upgraded {
+    kind: UpgradedKind,
+}
+
+enum UpgradedKind {
+    Hyper1(hyper_1::upgrade::Upgraded),
+    #[cfg(feature = "hyper-014")]
+    Hyper014(hyper_014::upgrade::Upgraded, crate::io::ReadBuffer),
+}
+
+impl fmt::Debug for Upgraded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Upgraded").finish()
+    }
+}
+
+impl tokio::io::AsyncRead for Upgraded {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match &mut self.get_mut().kind {
+            UpgradedKind::Hyper1(io) => poll_read_hyper1_as_tokio(Pin::new(io), cx, buf),
+            #[cfg(feature = "hyper-014")]
+            UpgradedKind::Hyper014(io, _) => tokio::io::AsyncRead::poll_read(Pin::new(io), cx, buf),
+        }
+    }
+}
+
+impl tokio::io::AsyncWrite for Upgraded {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match &mut self.get_mut().kind {
+            UpgradedKind::Hyper1(io) => hyper_1::rt::Write::poll_write(Pin::new(io), cx, buf),
+            #[cfg(feature = "hyper-014")]
+            UpgradedKind::Hyper014(io, _) => {
+                tokio::io::AsyncWrite::poll_write(Pin::new(io), cx, buf)
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match &mut self.get_mut().kind {
+            UpgradedKind::Hyper1(io) => hyper_1::rt::Write::poll_flush(Pin::new(io), cx),
+            #[cfg(feature = "hyper-014")]
+            UpgradedKind::Hyper014(io, _) => tokio::io::AsyncWrite::poll_flush(Pin::new(io), cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match &mut self.get_mut().kind {
+            UpgradedKind::Hyper1(io) => hyper_1::rt::Write::poll_shutdown(Pin::new(io), cx),
+            #[cfg(feature = "hyper-014")]
+            UpgradedKind::Hyper014(io, _) => tokio::io::AsyncWrite::poll_shutdown(Pin::new(io), cx),
+        }
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        match &mut self.get_mut().kind {
+            UpgradedKind::Hyper1(io) => {
+                hyper_1::rt::Write::poll_write_vectored(Pin::new(io), cx, bufs)
+            }
+            #[cfg(feature = "hyper-014")]
+            UpgradedKind::Hyper014(io, _) => {
+                tokio::io::AsyncWrite::poll_write_vectored(Pin::new(io), cx, bufs)
+            }
+        }
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        match &self.kind {
+            UpgradedKind::Hyper1(io) => hyper_1::rt::Write::is_write_vectored(io),
+            #[cfg(feature = "hyper-014")]
+            UpgradedKind::Hyper014(io, _) => tokio::io::AsyncWrite::is_write_vectored(io),
+        }
+    }
+}
+
+impl hyper_1::rt::Read for Upgraded {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: hyper_1::rt::ReadBufCursor<'_>,
+    ) -> Poll<io::Result<()>> {
+        match &mut self.get_mut().kind {
+            UpgradedKind::Hyper1(io) => hyper_1::rt::Read::poll_read(Pin::new(io), cx, buf),
+            #[cfg(feature = "hyper-014")]
+            UpgradedKind::Hyper014(io, read_buf) => {
+                crate::io::poll_read_tokio_as_hyper1(Pin::new(io), cx, read_buf, buf)
+            }
+        }
+    }
+}
+
+impl hyper_1::rt::Write for Upgraded {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        tokio::io::AsyncWrite::poll_write(self, cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        tokio::io::AsyncWrite::poll_flush(self, cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        tokio::io::AsyncWrite::poll_shutdown(self, cx)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        tokio::io::AsyncWrite::is_write_vectored(self)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        tokio::io::AsyncWrite::poll_write_vectored(self, cx, bufs)
+    }
+}
+
+/// A [tower-service 0.3 `Service`] that makes upgrades received by hyper 1.0 available as hyper
+/// 0.14's `OnUpgrade`.
+///
+/// Code written for hyper 0.14, such as axum 0.6's `WebSocketUpgrade` extractor, removes hyper
+/// 0.14's `OnUpgrade` from the request extensions and fails if it isn't there. hyper 0.14's
+/// `OnUpgrade` can't be constructed outside of hyper, so for every request that hyper 1.0 can
+/// upgrade, this service upgrades an in-memory connection with a hyper 0.14 client and inserts
+/// the resulting `OnUpgrade` into the request extensions. Once hyper 1.0 has upgraded the real
+/// connection, data is copied between the two.
+///
+/// Other requests are passed through unchanged. This must be used from within a tokio runtime
+/// and requires the `hyper-014` feature.
+///
+/// # Example
+///
+/// ```no_run
+/// # use hyper_1 as hyper;
+/// use axum::{
+///     extract::ws::{WebSocket, WebSocketUpgrade},
+///     response::Response,
+///     routing::get,
+///     Router,
+/// };
+/// use hyper::server::conn::http1;
+/// use tokio::net::TcpListener;
+/// use tower_hyper_http_body_compat::{
+///     upgrade::AddHyper014OnUpgrade, TokioIoToHyper1Io,
+///     TowerService03HttpServiceAsHyper1HttpService,
+/// };
+///
+/// async fn handler(ws: WebSocketUpgrade) -> Response {
+///     ws.on_upgrade(|socket: WebSocket| async move {
+///         // ...
+///     })
+/// }
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///     let app = Router::new().route("/ws", get(handler));
+///     let service =
+///         TowerService03HttpServiceAsHyper1HttpService::new(AddHyper014OnUpgrade::new(app));
+///
+///     let tcp_listener = TcpListener::bind("0.0.0.0:8080").await?;
+///     loop {
+///         let (tcp_stream, _) = tcp_listener.accept().await?;
+///         let service = service.clone();
+///         tokio::spawn(async move {
+///             if let Err(http_err) = http1::Builder::new()
+///                 .serve_connection(TokioIoToHyper1Io::new(tcp_stream), service)
+///                 .with_upgrades()
+///                 .await
+///             {
+///                 eprintln!("Error while serving HTTP connection: {}", http_err);
+///             }
+///         });
+///     }
+/// }
+/// ```
+///
+/// [tower-service 0.3 `Service`]: https://docs.rs/tower-service/latest/tower_service/trait.Service.html
+#[cfg(feature = "hyper-014")]
+#[derive(Clone, Copy, Debug)]
+pub struct AddHyper014OnUpgrade<S> {
+    service: S,
+}
+
+#[cfg(feature = "hyper-014")]
+impl<S> AddHyper014OnUpgrade<S> {
+    /// Create a new `AddHyper014OnUpgrade`.
+    #[inline]
+    pub fn new(service: S) -> Self {
+        Self { service }
+    }
+}
+
+#[cfg(feature = "hyper-014")]
+impl<S, B> tower_service_03::Service<http::Request<B>> for AddHyper014OnUpgrade<S>
+where
+    S: tower_service_03::Service<http::Request<B>> + Clone,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = AddHyper014OnUpgradeFuture<S, B>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
+        if !has_hyper_1_on_upgrade(req.extensions()) {
+            return AddHyper014OnUpgradeFuture {
+                state: AddHyper014OnUpgradeState::Calling {
+                    future: self.service.call(req),
+                },
+            };
+        }
+
+        // the upgrade has to be prepared before calling the service so take the service that
+        // has been driven to readiness
+        let clone = self.service.clone();
+        let service = std::mem::replace(&mut self.service, clone);
+
+        let on_upgrade = on(&mut req);
+        AddHyper014OnUpgradeFuture {
+            state: AddHyper014OnUpgradeState::Upgrading {
+                future: Box::pin(hyper_014_on_upgrade(on_upgrade)),
+                service,
+                req: Some(req),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "hyper-014")]
+pin_project! {
+    /// Response future for [`AddHyper014OnUpgrade`].
+    pub struct AddHyper014OnUpgradeFuture<S, B>
+    where
+        S: tower_service_03::Service<http::Request<B>>,
+    {
+        #[pin]
+        state: AddHyper014OnUpgradeState<S, B>,
+    }
+}
+
+#[cfg(feature = "hyper-014")]
+pin_project! {
+    #[project = AddHyper014OnUpgradeStateProj]
+    enum AddHyper014OnUpgradeState<S, B>
+    where
+        S: tower_service_03::Service<http::Request<B>>,
+    {
+        Upgrading {
+            future: Pin<Box<dyn Future<Output = Option<hyper_014::upgrade::OnUpgrade>> + Send>>,
+            service: S,
+            req: Option<http::Request<B>>,
+        },
+        Calling {
+            #[pin]
+            future: S::Future,
+        },
+    }
+}
+
+#[cfg(feature = "hyper-014")]
+impl<S, B> Future for AddHyper014OnUpgradeFuture<S, B>
+where
+    S: tower_service_03::Service<http::Request<B>>,
+{
+    type Output = Result<S::Response, S::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            let mut state = self.as_mut().project().state;
+            match state.as_mut().project() {
+                AddHyper014OnUpgradeStateProj::Upgrading {
+                    future,
+                    service,
+                    req,
+                } => {
+                    let on_upgrade = ready!(future.as_mut().poll(cx));
+                    let mut req = req.take().expect("future polled after completion");
+                    if let Some(on_upgrade) = on_upgrade {
+                        req.extensions_mut().insert(on_upgrade);
+                    }
+                    let future = service.call(req);
+                    state.set(AddHyper014OnUpgradeState::Calling { future });
+                }
+                AddHyper014OnUpgradeStateProj::Calling { future } => return future.poll(cx),
+            }
+        }
+    }
+}
+
+/// Upgrade an in-memory connection with a hyper 0.14 client and copy data between it and
+/// `on_upgrade` once that resolves.
+#[cfg(feature = "hyper-014")]
+async fn hyper_014_on_upgrade(on_upgrade: OnUpgrade) -> Option<hyper_014::upgrade::OnUpgrade> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let (client_io, mut server_io) = tokio::io::duplex(16 * 1024);
+
+    tokio::spawn(async move {
+        // wait for the client's request and accept the upgrade right away
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            let mut byte = [0; 1];
+            match server_io.read(&mut byte).await {
+                Ok(1) => head.push(byte[0]),
+                _ => return,
+            }
+        }
+        let accepted = server_io
+            .write_all(b"HTTP/1.1 101 Switching Protocols\r\nconnection: upgrade\r\nupgrade: hyper-1\r\n\r\n")
+            .await;
+        if accepted.is_err() {
+            return;
+        }
+
+        if let Ok(mut upgraded) = on_upgrade.await {
+            let _ = tokio::io::copy_bidirectional(&mut server_io, &mut upgraded).await;
+        }
+    });
+
+    let (mut send_request, conn) = hyper_014::client::conn::handshake(client_io).await.ok()?;
+    tokio::spawn(conn);
+
+    let req = http::Request::builder()
+        .header(http::header::CONNECTION, "upgrade")
+        .header(http::header::UPGRADE, "hyper-1")
+        .body(hyper_014::Body::empty())
+        .unwrap();
+    let res = send_request.send_request(req).await.ok()?;
+    Some(hyper_014::upgrade::on(res))
+}
+
+/// A [tower-layer 0.3 `Layer`] that produces [`AddHyper014OnUpgrade`]s.
+///
+/// [tower-layer 0.3 `Layer`]: https://docs.rs/tower-layer/latest/tower_layer/trait.Layer.html
+#[cfg(feature = "hyper-014")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AddHyper014OnUpgradeLayer {
+    _priv: (),
+}
+
+#[cfg(feature = "hyper-014")]
+impl AddHyper014OnUpgradeLayer {
+    /// Create a new `AddHyper014OnUpgradeLayer`.
+    #[inline]
+    pub fn new() -> Self {
+        Self { _priv: () }
+    }
+}
+
+#[cfg(feature = "hyper-014")]
+impl<S> tower_layer_03::Layer<S> for AddHyper014OnUpgradeLayer {
+    type Service = AddHyper014OnUpgrade<S>;
+
+    #[inline]
+    fn layer(&self, inner: S) -> Self::Service {
+        AddHyper014OnUpgrade::new(inner)
+    }
+}
+
+#[derive(Debug)]
+struct NoUpgrade;
+
+impl fmt::Display for NoUpgrade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no upgrade available for this request")
+    }
+}
+
+impl std::error::Error for NoUpgrade {}